@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Entry {
+    day: u32,
+    part: usize,
+    input: String,
+    version: String,
+    answer: String,
+}
+
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.splitn(5, '\t');
+
+    Some(Entry {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        input: fields.next()?.to_string(),
+        version: fields.next()?.to_string(),
+        answer: fields.next()?.to_string(),
+    })
+}
+
+impl Cache {
+    pub fn load(path: PathBuf) -> Cache {
+        let entries = std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(parse_entry)
+            .collect();
+
+        Cache { path, entries }
+    }
+
+    pub fn get(&self, day: u32, input: &str, version: &str) -> Option<Vec<String>> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.day == day && entry.input == input && entry.version == version)
+            .collect();
+        entries.sort_by_key(|entry| entry.part);
+
+        match entries.is_empty() {
+            true => None,
+            false => Some(entries.iter().map(|entry| entry.answer.clone()).collect()),
+        }
+    }
+
+    pub fn store(
+        &mut self,
+        day: u32,
+        input: &str,
+        version: &str,
+        answers: &[String],
+    ) -> std::io::Result<()> {
+        self.entries
+            .retain(|entry| entry.day != day || (entry.version == version && entry.input != input));
+        self.entries
+            .extend(answers.iter().enumerate().map(|(idx, answer)| Entry {
+                day,
+                part: idx + 1,
+                input: input.to_string(),
+                version: version.to_string(),
+                answer: answer.clone(),
+            }));
+
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\n",
+                    entry.day, entry.part, entry.input, entry.version, entry.answer
+                )
+            })
+            .collect();
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+
+    fn get_cache(name: &str) -> Cache {
+        Cache::load(stub::get_temp_dir(name).join("results.tsv"))
+    }
+
+    fn answers(answers: &[&str]) -> Vec<String> {
+        answers.iter().map(|answer| answer.to_string()).collect()
+    }
+
+    #[test]
+    fn store_and_load() {
+        let mut cache = get_cache("cache-roundtrip");
+        cache
+            .store(14, "abc", "v1", &answers(&["136", "64"]))
+            .unwrap();
+
+        let loaded = Cache::load(cache.path.clone());
+        assert_eq!(loaded.get(14, "abc", "v1"), Some(answers(&["136", "64"])));
+        assert_eq!(loaded.get(14, "def", "v1"), None);
+        assert_eq!(loaded.get(16, "abc", "v1"), None);
+    }
+
+    #[test]
+    fn new_version_invalidates() {
+        let mut cache = get_cache("cache-version");
+        cache.store(14, "abc", "v1", &answers(&["1", "2"])).unwrap();
+        cache.store(14, "def", "v1", &answers(&["3", "4"])).unwrap();
+        cache.store(14, "abc", "v2", &answers(&["5", "6"])).unwrap();
+
+        assert_eq!(cache.get(14, "abc", "v1"), None);
+        assert_eq!(cache.get(14, "def", "v1"), None);
+        assert_eq!(cache.get(14, "abc", "v2"), Some(answers(&["5", "6"])));
+    }
+
+    #[test]
+    fn inputs_are_cached_separately() {
+        let mut cache = get_cache("cache-inputs");
+        cache.store(14, "abc", "v1", &answers(&["1", "2"])).unwrap();
+        cache.store(14, "def", "v1", &answers(&["3"])).unwrap();
+        cache.store(14, "abc", "v1", &answers(&["7", "8"])).unwrap();
+
+        assert_eq!(cache.get(14, "abc", "v1"), Some(answers(&["7", "8"])));
+        assert_eq!(cache.get(14, "def", "v1"), Some(answers(&["3"])));
+    }
+}
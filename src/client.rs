@@ -0,0 +1,278 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str = "github.com/YigitDincer/aoc23 input fetcher";
+const YEAR: u32 = 2023;
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+static THROTTLE: Mutex<()> = Mutex::new(());
+
+#[derive(Debug)]
+pub struct Client {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub user_agent: String,
+    pub state_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Status { url: String, status: u16 },
+    Transport { url: String, message: String },
+    Io(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token, set AOC_SESSION or write it to .aoc-session"
+            ),
+            ClientError::Status { url, status } => write!(f, "{} returned HTTP {}", url, status),
+            ClientError::Transport { url, message } => write!(f, "{}: {}", url, message),
+            ClientError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+fn read_session(paths: &[PathBuf]) -> Option<String> {
+    std::env::var("AOC_SESSION")
+        .ok()
+        .into_iter()
+        .chain(
+            paths
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok()),
+        )
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+}
+
+fn get_wait_time(last_request: Duration, now: Duration, min_interval: Duration) -> Duration {
+    (last_request + min_interval).saturating_sub(now)
+}
+
+impl Client {
+    pub fn from_env() -> Client {
+        let mut session_paths = vec![PathBuf::from(".aoc-session")];
+        if let Ok(home) = std::env::var("HOME") {
+            session_paths.push(PathBuf::from(home).join(".config/aoc23/session"));
+        }
+
+        Client {
+            base_url: std::env::var("AOC_BASE_URL").unwrap_or(DEFAULT_BASE_URL.to_string()),
+            session: read_session(&session_paths),
+            user_agent: std::env::var("AOC_USER_AGENT").unwrap_or(DEFAULT_USER_AGENT.to_string()),
+            state_dir: PathBuf::from(".aoc23"),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    pub fn day_url(&self, day: u32) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url.trim_end_matches('/'),
+            YEAR,
+            day
+        )
+    }
+
+    fn throttle(&self) -> Result<(), ClientError> {
+        let _guard = THROTTLE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = self.state_dir.join("last-request");
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+        let last_request = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(last_request) = last_request {
+            std::thread::sleep(get_wait_time(last_request, now(), self.min_interval));
+        }
+
+        std::fs::create_dir_all(&self.state_dir)
+            .and_then(|_| std::fs::write(&path, now().as_millis().to_string()))
+            .map_err(|error| ClientError::Io(path, error))
+    }
+
+    fn request(&self, method: &str, url: &str) -> Result<ureq::Request, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::MissingSession)?;
+        self.throttle()?;
+
+        Ok(ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&self.user_agent)
+            .build()
+            .request(method, url)
+            .set("Cookie", &format!("session={}", session)))
+    }
+
+    fn read_response(
+        url: &str,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, ClientError> {
+        let transport = |message: String| ClientError::Transport {
+            url: url.to_string(),
+            message,
+        };
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|error| transport(error.to_string())),
+            Err(ureq::Error::Status(status, _)) => Err(ClientError::Status {
+                url: url.to_string(),
+                status,
+            }),
+            Err(error) => Err(transport(error.to_string())),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Result<String, ClientError> {
+        Client::read_response(url, self.request("GET", url)?.call())
+    }
+
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        Client::read_response(url, self.request("POST", url)?.send_form(form))
+    }
+}
+
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    use super::Client;
+
+    pub fn get_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc23-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request = String::new();
+                    let mut content_length = 0;
+
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                    }
+                    let mut content = vec![0; content_length];
+                    reader.read_exact(&mut content).unwrap();
+                    request.push_str(&String::from_utf8(content).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    pub fn get_test_client(base_url: &str, name: &str) -> Client {
+        Client {
+            base_url: base_url.to_string(),
+            session: Some("abc123".to_string()),
+            user_agent: "aoc23 tests".to_string(),
+            state_dir: get_temp_dir(name),
+            min_interval: Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_wait_time() {
+        let second = Duration::from_secs(1);
+
+        assert_eq!(
+            super::get_wait_time(second * 10, second * 12, second * 5),
+            second * 3
+        );
+        assert_eq!(
+            super::get_wait_time(second * 10, second * 20, second * 5),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn get_sends_session_and_user_agent() {
+        let (base_url, server) = stub::serve(vec![(200, "1 2 3\n".to_string())]);
+        let client = stub::get_test_client(&base_url, "client-get");
+
+        assert_eq!(
+            client.get(&format!("{}/input", client.day_url(1))).unwrap(),
+            "1 2 3\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/1/input "));
+        assert!(requests[0].contains("Cookie: session=abc123"));
+        assert!(requests[0].contains("User-Agent: aoc23 tests"));
+        assert!(client.state_dir.join("last-request").exists());
+    }
+
+    #[test]
+    fn get_reports_status() {
+        let (base_url, server) = stub::serve(vec![(404, "Not Found".to_string())]);
+        let client = stub::get_test_client(&base_url, "client-status");
+
+        assert!(matches!(
+            client.get(&base_url),
+            Err(ClientError::Status { status: 404, .. })
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn get_without_session() {
+        let mut client = stub::get_test_client("http://127.0.0.1:9", "client-session");
+        client.session = None;
+
+        assert!(matches!(
+            client.get("http://127.0.0.1:9"),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
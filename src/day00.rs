@@ -1,22 +1,41 @@
+use crate::solver::{Options, Solution};
+
 fn parse(input: &str) -> Vec<i32> {
     input.lines().map(|line| line.parse().unwrap()).collect()
 }
 
-fn add_numbers(input: Vec<i32>) -> i32 {
-    input.iter().sum()
+fn solve_1(input: &str) -> i32 {
+    parse(input).iter().sum()
+}
+
+fn solve_2(input: &str) -> i32 {
+    parse(input).iter().product()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", add_numbers(parse(input)))
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(input).to_string(), solve_2(input).to_string()].into())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::solver::Options;
+
+    #[test]
+    fn solve() {
+        assert_eq!(
+            super::solve(EXAMPLE_INPUTS, &Options::default()),
+            Ok(vec!["6".to_string(), "6".to_string()].into())
+        );
+    }
+
+    #[test]
+    fn solve_1() {
+        assert_eq!(super::solve_1(EXAMPLE_INPUTS), 6);
+    }
 
     #[test]
-    fn example() {
-        assert_eq!(add_numbers(vec![1, 2, 3]), 6);
+    fn solve_2() {
+        assert_eq!(super::solve_2(EXAMPLE_INPUTS), 6);
     }
 
     #[test]
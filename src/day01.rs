@@ -1,36 +1,185 @@
-fn solve_1(input: &str) -> u32 {
-    input.lines().map(extract_first_and_last_digits).sum()
+use std::collections::{HashMap, VecDeque};
+
+use crate::solver::{Options, Solution};
+
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const DIGIT_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+#[derive(Debug)]
+struct Automaton {
+    transitions: Vec<HashMap<char, usize>>,
+    fail: Vec<usize>,
+    matches: Vec<Vec<(usize, u32)>>,
+    max_len: usize,
 }
 
-fn extract_first_and_last_digits(input: &str) -> u32 {
-    let v: Vec<_> = input.chars().filter_map(|a| a.to_digit(10)).collect();
-    v.first().unwrap() * 10 + v.last().unwrap()
+impl Automaton {
+    fn new(words: impl Iterator<Item = (Vec<char>, u32)>) -> Automaton {
+        let mut automaton = Automaton {
+            transitions: vec![HashMap::new()],
+            fail: vec![0],
+            matches: vec![vec![]],
+            max_len: 0,
+        };
+
+        for (word, value) in words {
+            let mut state = 0;
+            for &ch in &word {
+                state = match automaton.transitions[state].get(&ch) {
+                    Some(&next) => next,
+                    None => {
+                        automaton.transitions.push(HashMap::new());
+                        automaton.fail.push(0);
+                        automaton.matches.push(vec![]);
+                        let next = automaton.transitions.len() - 1;
+                        automaton.transitions[state].insert(ch, next);
+                        next
+                    }
+                };
+            }
+            automaton.matches[state].push((word.len(), value));
+            automaton.max_len = automaton.max_len.max(word.len());
+        }
+
+        let mut queue: VecDeque<_> = automaton.transitions[0].values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let transitions: Vec<_> = automaton.transitions[state]
+                .iter()
+                .map(|(&ch, &next)| (ch, next))
+                .collect();
+
+            for (ch, next) in transitions {
+                let mut fallback = automaton.fail[state];
+                while fallback != 0 && !automaton.transitions[fallback].contains_key(&ch) {
+                    fallback = automaton.fail[fallback];
+                }
+                let fail = automaton.transitions[fallback]
+                    .get(&ch)
+                    .copied()
+                    .unwrap_or(0);
+
+                automaton.fail[next] = fail;
+                let inherited = automaton.matches[fail].clone();
+                automaton.matches[next].extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        automaton
+    }
+
+    fn step(&self, mut state: usize, ch: char) -> usize {
+        loop {
+            if let Some(&next) = self.transitions[state].get(&ch) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    fn find_first(&self, text: impl Iterator<Item = char>) -> Option<u32> {
+        let mut state = 0;
+        let mut best: Option<(usize, usize, u32)> = None;
+
+        for (idx, ch) in text.enumerate() {
+            if best.is_some_and(|(start, _, _)| idx >= start + self.max_len) {
+                break;
+            }
+
+            state = self.step(state, ch);
+            for &(len, value) in &self.matches[state] {
+                let start = idx + 1 - len;
+                if best.is_none_or(|(best_start, best_len, _)| {
+                    start < best_start || (start == best_start && len > best_len)
+                }) {
+                    best = Some((start, len, value));
+                }
+            }
+        }
+
+        best.map(|(_, _, value)| value)
+    }
 }
 
-fn solve_2(input: &str) -> u32 {
+#[derive(Debug)]
+struct Tokenizer {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Tokenizer {
+    fn new(vocabulary: &[(&str, u32)]) -> Tokenizer {
+        Tokenizer {
+            forward: Automaton::new(
+                vocabulary
+                    .iter()
+                    .map(|&(word, value)| (word.chars().collect(), value)),
+            ),
+            backward: Automaton::new(
+                vocabulary
+                    .iter()
+                    .map(|&(word, value)| (word.chars().rev().collect(), value)),
+            ),
+        }
+    }
+
+    fn first(&self, input: &str) -> Option<u32> {
+        self.forward.find_first(input.chars())
+    }
+
+    fn last(&self, input: &str) -> Option<u32> {
+        self.backward.find_first(input.chars().rev())
+    }
+}
+
+fn get_calibration_value(tokenizer: &Tokenizer, input: &str) -> Option<u32> {
+    let first = tokenizer.first(input)?;
+    let last = tokenizer.last(input)?;
+
+    Some(first * 10_u32.pow(last.checked_ilog10().unwrap_or(0) + 1) + last)
+}
+
+fn solve_for(input: &str, tokenizer: &Tokenizer) -> u32 {
     input
         .lines()
-        .map(replace_text)
-        .map(|a| extract_first_and_last_digits(a.as_str()))
+        .filter_map(|line| get_calibration_value(tokenizer, line))
         .sum()
 }
 
-fn replace_text(input: &str) -> String {
-    input
-        .replace("one", "one1one")
-        .replace("two", "two2two")
-        .replace("three", "three3three")
-        .replace("four", "four4four")
-        .replace("five", "five5five")
-        .replace("six", "six6six")
-        .replace("seven", "seven7seven")
-        .replace("eight", "eight8eight")
-        .replace("nine", "nine9nine")
+fn solve_1(input: &str) -> u32 {
+    solve_for(input, &Tokenizer::new(&DIGITS))
+}
+
+fn solve_2(input: &str) -> u32 {
+    solve_for(input, &Tokenizer::new(&[DIGITS, DIGIT_WORDS].concat()))
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_1(input));
-    println!("{}", solve_2(input));
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(input).to_string(), solve_2(input).to_string()].into())
 }
 
 #[cfg(test)]
@@ -39,13 +188,47 @@ mod tests {
 
     #[test]
     fn example() {
-        assert_eq!(extract_first_and_last_digits("1abc2"), 12);
-        assert_eq!(extract_first_and_last_digits("a1b2c3d4e5f"), 15);
+        let tokenizer = Tokenizer::new(&DIGITS);
+
+        assert_eq!(get_calibration_value(&tokenizer, "1abc2"), Some(12));
+        assert_eq!(get_calibration_value(&tokenizer, "a1b2c3d4e5f"), Some(15));
+        assert_eq!(get_calibration_value(&tokenizer, "treb7uchet"), Some(77));
+        assert_eq!(get_calibration_value(&tokenizer, "abc"), None);
     }
 
     #[test]
     fn example_part_2() {
         assert_eq!(solve_2("zoneight"), 18);
         assert_eq!(solve_2("eighthree"), 83);
+        assert_eq!(
+            solve_2(
+                "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"
+            ),
+            281
+        );
+    }
+
+    #[test]
+    fn overlapping_tokens() {
+        let tokenizer = Tokenizer::new(&[("abcd", 1), ("bc", 2), ("cde", 3)]);
+
+        assert_eq!(tokenizer.first("xabcde"), Some(1));
+        assert_eq!(tokenizer.last("xabcde"), Some(3));
+        assert_eq!(tokenizer.first("xbcde"), Some(2));
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        let tokenizer = Tokenizer::new(&[("eins", 1), ("zwei", 2), ("zehn", 10), ("elf", 11)]);
+
+        assert_eq!(get_calibration_value(&tokenizer, "zweinszehn"), Some(210));
+        assert_eq!(get_calibration_value(&tokenizer, "elfeins"), Some(111));
+        assert_eq!(solve_for("zweins\nelf", &tokenizer), 21 + 1111);
     }
 }
@@ -1,8 +1,10 @@
-const CUBES_IN_BAG: Cubes = Cubes {
-    red: 12,
-    green: 13,
-    blue: 14,
-};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::solver::{Options, Solution};
+
+fn get_default_bag() -> Cubes {
+    Cubes::from([("red", 12), ("green", 13), ("blue", 14)])
+}
 
 fn parse_game_line(game_line: &str) -> Game {
     let (game_info, contents_str) = game_line.split_once(": ").unwrap();
@@ -14,13 +16,7 @@ fn parse_game_line(game_line: &str) -> Game {
             let mut content = Cubes::default();
             content_str.split(", ").for_each(|one_pair| {
                 let (num, color) = one_pair.split_once(' ').unwrap();
-                let num = num.parse().unwrap();
-                match color {
-                    "red" => content.red = num,
-                    "green" => content.green = num,
-                    "blue" => content.blue = num,
-                    _ => panic!("wrong color"),
-                }
+                content.add(color, num.parse().unwrap());
             });
             content
         })
@@ -32,46 +28,163 @@ fn parse_game_line(game_line: &str) -> Game {
     }
 }
 
+fn parse_bag(input: &str) -> Result<Cubes, String> {
+    let mut bag = Cubes::default();
+    for pair in input.split(',') {
+        let (color, num) = pair
+            .split_once('=')
+            .and_then(|(color, num)| Some((color.trim(), num.trim().parse().ok()?)))
+            .filter(|(color, _)| !color.is_empty())
+            .ok_or(format!(
+                "invalid bag entry {:?}, expected color=count",
+                pair.trim()
+            ))?;
+        bag.add(color, num);
+    }
+    Ok(bag)
+}
+
 fn get_min_cubes_required(draws: &[Cubes]) -> Cubes {
-    Cubes {
-        red: draws.iter().map(|d| d.red).max().unwrap(),
-        green: draws.iter().map(|d| d.green).max().unwrap(),
-        blue: draws.iter().map(|d| d.blue).max().unwrap(),
+    let mut min_cubes = Cubes::default();
+    for (color, &count) in draws.iter().flat_map(|draw| &draw.counts) {
+        let current = min_cubes.counts.entry(color.clone()).or_default();
+        *current = (*current).max(count);
     }
+    min_cubes
 }
 
 fn is_draw_possible(draw: &Cubes, reference: &Cubes) -> bool {
-    reference.red >= draw.red && reference.green >= draw.green && reference.blue >= draw.blue
+    draw.counts
+        .iter()
+        .all(|(color, &count)| reference.get(color) >= count)
 }
 
-fn solve_1(input: &str) -> u32 {
+fn get_possible_games_by_limit(games: &[Game], bag: &Cubes, color: &str) -> Vec<(u32, u32)> {
+    let mut thresholds: Vec<_> = games
+        .iter()
+        .map(|game| (get_min_cubes_required(&game.draws), game.id))
+        .filter(|(min_cubes, _)| {
+            min_cubes
+                .counts
+                .iter()
+                .all(|(other, &count)| other == color || bag.get(other) >= count)
+        })
+        .map(|(min_cubes, id)| (min_cubes.get(color), id))
+        .collect();
+    thresholds.sort();
+    thresholds
+}
+
+fn get_color_stats(games: &[Game]) -> BTreeMap<String, ColorStats> {
+    let mut stats: BTreeMap<String, ColorStats> = BTreeMap::new();
+
+    for game in games {
+        for (color, &count) in &get_min_cubes_required(&game.draws).counts {
+            let color_stats = stats.entry(color.clone()).or_default();
+            color_stats.games += 1;
+            color_stats.max = color_stats.max.max(count);
+        }
+        for (color, &count) in game.draws.iter().flat_map(|draw| &draw.counts) {
+            stats.entry(color.clone()).or_default().total += count;
+        }
+    }
+
+    stats
+}
+
+fn get_power(cubes: &Cubes, bag: &Cubes) -> u32 {
+    let colors: BTreeSet<_> = bag.counts.keys().chain(cubes.counts.keys()).collect();
+    colors.into_iter().map(|color| cubes.get(color)).product()
+}
+
+fn solve_1(input: &str, bag: &Cubes) -> u32 {
     input
         .lines()
         .map(parse_game_line)
-        .filter(|game| is_draw_possible(&get_min_cubes_required(&game.draws), &CUBES_IN_BAG))
+        .filter(|game| is_draw_possible(&get_min_cubes_required(&game.draws), bag))
         .map(|game| game.id)
         .sum()
 }
 
-fn solve_2(input: &str) -> u32 {
+fn solve_2(input: &str, bag: &Cubes) -> u32 {
     input
         .lines()
         .map(parse_game_line)
-        .map(|game| get_min_cubes_required(&game.draws))
-        .map(|cubes| cubes.red * cubes.green * cubes.blue)
+        .map(|game| get_power(&get_min_cubes_required(&game.draws), bag))
         .sum()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_1(input));
-    println!("{}", solve_2(input));
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let bag = options
+        .get("--bag")?
+        .map_or_else(|| Ok(get_default_bag()), parse_bag)?;
+
+    let mut solution = Solution::from(vec![
+        solve_1(input, &bag).to_string(),
+        solve_2(input, &bag).to_string(),
+    ]);
+
+    let games: Vec<_> = input.lines().map(parse_game_line).collect();
+    if options.flag("--stats") {
+        for game in &games {
+            solution.note(format!(
+                "Game {}: {}",
+                game.id,
+                get_min_cubes_required(&game.draws)
+            ));
+        }
+        for (color, stats) in get_color_stats(&games) {
+            solution.note(format!(
+                "{}: total {}, max {}, in {} games",
+                color, stats.total, stats.max, stats.games
+            ));
+        }
+    }
+    if let Some(color) = options.get("--vary")? {
+        for (limit, id) in get_possible_games_by_limit(&games, &bag, color) {
+            solution.note(format!("{} >= {}: game {}", color, limit, id));
+        }
+    }
+
+    Ok(solution)
 }
 
-#[derive(PartialEq, Eq, Debug, Default)]
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
 struct Cubes {
-    red: u32,
-    green: u32,
-    blue: u32,
+    counts: BTreeMap<String, u32>,
+}
+
+impl Cubes {
+    fn get(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    fn add(&mut self, color: &str, count: u32) {
+        if count > 0 {
+            *self.counts.entry(color.to_string()).or_default() += count;
+        }
+    }
+}
+
+impl<const N: usize> From<[(&str, u32); N]> for Cubes {
+    fn from(pairs: [(&str, u32); N]) -> Self {
+        let mut cubes = Cubes::default();
+        pairs
+            .into_iter()
+            .for_each(|(color, count)| cubes.add(color, count));
+        cubes
+    }
+}
+
+impl std::fmt::Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let pairs: Vec<_> = self
+            .counts
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect();
+        write!(f, "{}", pairs.join(", "))
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -80,27 +193,24 @@ struct Game {
     draws: Vec<Cubes>,
 }
 
+#[derive(PartialEq, Eq, Debug, Default)]
+struct ColorStats {
+    total: u32,
+    max: u32,
+    games: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const GAME_1_DRAWS: [Cubes; 3] = [
-        Cubes {
-            red: 4,
-            green: 0,
-            blue: 3,
-        },
-        Cubes {
-            red: 1,
-            green: 2,
-            blue: 6,
-        },
-        Cubes {
-            red: 0,
-            green: 2,
-            blue: 0,
-        },
-    ];
+    fn get_game_1_draws() -> Vec<Cubes> {
+        vec![
+            Cubes::from([("red", 4), ("green", 0), ("blue", 3)]),
+            Cubes::from([("red", 1), ("green", 2), ("blue", 6)]),
+            Cubes::from([("red", 0), ("green", 2), ("blue", 0)]),
+        ]
+    }
 
     const EXAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -108,36 +218,120 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
+    fn get_example_games() -> Vec<Game> {
+        EXAMPLE_INPUT.lines().map(parse_game_line).collect()
+    }
+
     #[test]
     fn example() {
         assert_eq!(
             parse_game_line(EXAMPLE_INPUT.lines().next().unwrap()),
             Game {
                 id: 1,
-                draws: GAME_1_DRAWS.into(),
+                draws: get_game_1_draws(),
             }
         );
     }
 
+    #[test]
+    fn parse_unknown_color() {
+        assert_eq!(
+            parse_game_line("Game 7: 2 purple, 1 red").draws,
+            vec![Cubes::from([("purple", 2), ("red", 1)])]
+        );
+    }
+
     #[test]
     fn count_valid_game_ids() {
-        assert_eq!(solve_1(EXAMPLE_INPUT), 8);
+        assert_eq!(solve_1(EXAMPLE_INPUT, &get_default_bag()), 8);
+    }
+
+    #[test]
+    fn count_valid_game_ids_with_custom_bag() {
+        assert_eq!(
+            solve_1(
+                EXAMPLE_INPUT,
+                &parse_bag("red=20, green=13, blue=15").unwrap()
+            ),
+            15
+        );
     }
 
     #[test]
     fn get_min_cubes_required() {
         assert_eq!(
-            super::get_min_cubes_required(&GAME_1_DRAWS),
-            Cubes {
-                red: 4,
-                green: 2,
-                blue: 6
-            }
+            super::get_min_cubes_required(&get_game_1_draws()),
+            Cubes::from([("red", 4), ("green", 2), ("blue", 6)])
         );
     }
 
     #[test]
     fn solve_2() {
-        assert_eq!(super::solve_2(EXAMPLE_INPUT), 2286);
+        assert_eq!(super::solve_2(EXAMPLE_INPUT, &get_default_bag()), 2286);
+        assert_eq!(
+            super::solve_2("Game 1: 3 red; 2 green", &get_default_bag()),
+            0
+        );
+    }
+
+    #[test]
+    fn solve_vary_without_stats() {
+        let options = Options::from([("--vary", Some("red"))]);
+
+        assert_eq!(
+            super::solve(EXAMPLE_INPUT, &options).unwrap().notes,
+            vec![
+                "red >= 1: game 2",
+                "red >= 4: game 1",
+                "red >= 6: game 5",
+                "red >= 20: game 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn get_power_uses_bag_and_game_colors() {
+        let bag = get_default_bag();
+
+        assert_eq!(
+            get_power(
+                &Cubes::from([("purple", 2), ("red", 3), ("green", 1), ("blue", 4)]),
+                &bag
+            ),
+            24
+        );
+        assert_eq!(get_power(&Cubes::from([("red", 3), ("blue", 4)]), &bag), 0);
+    }
+
+    #[test]
+    fn parse_bag_rejects_invalid_entries() {
+        assert_eq!(
+            parse_bag("red=12, green"),
+            Err("invalid bag entry \"green\", expected color=count".to_string())
+        );
+        assert!(parse_bag("red=lots").is_err());
+        assert!(parse_bag("=3").is_err());
+    }
+
+    #[test]
+    fn get_possible_games_by_limit() {
+        assert_eq!(
+            super::get_possible_games_by_limit(&get_example_games(), &get_default_bag(), "red"),
+            vec![(1, 2), (4, 1), (6, 5), (20, 3)]
+        );
+    }
+
+    #[test]
+    fn get_color_stats() {
+        let stats = super::get_color_stats(&get_example_games());
+
+        assert_eq!(
+            stats["red"],
+            ColorStats {
+                total: 61,
+                max: 20,
+                games: 5,
+            }
+        );
     }
 }
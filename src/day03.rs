@@ -1,5 +1,6 @@
-use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use crate::solver::{Options, Solution};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Number {
@@ -7,135 +8,142 @@ struct Number {
     pos: Vec<(i32, i32)>,
 }
 
-type EngineSchema = HashMap<(i32, i32), char>;
-
-fn parse_input(input: &str) -> EngineSchema {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row_idx, row)| {
-            (0..row.len())
-                .filter(|&col_idx| row.as_bytes()[col_idx] as char != '.')
-                .map(move |col_idx| {
-                    (
-                        (row_idx as i32, col_idx as i32),
-                        row.as_bytes()[col_idx] as char,
-                    )
-                })
-        })
-        .collect()
+#[derive(Debug, Default)]
+struct SchematicIndex {
+    numbers: Vec<Number>,
+    number_at: HashMap<(i32, i32), usize>,
+    symbols: HashMap<(i32, i32), char>,
+    adjacent_numbers: HashMap<(i32, i32), Vec<usize>>,
 }
 
-fn get_all_numbers(input_as_hashmap: &EngineSchema) -> EngineSchema {
-    input_as_hashmap
-        .iter()
-        .filter(|(_, ch)| ch.is_digit(10))
-        .map(|(&key, &ch)| (key, ch))
-        .collect()
+fn get_all_neighbor_pos((row, col): (i32, i32)) -> [(i32, i32); 8] {
+    [
+        (row - 1, col - 1),
+        (row - 1, col),
+        (row - 1, col + 1),
+        (row, col - 1),
+        (row, col + 1),
+        (row + 1, col - 1),
+        (row + 1, col),
+        (row + 1, col + 1),
+    ]
 }
 
-fn get_all_non_numbers(input_as_hashmap: &EngineSchema) -> EngineSchema {
-    input_as_hashmap
-        .iter()
-        .filter(|(_, ch)| !ch.is_digit(10))
-        .map(|(&key, &ch)| (key, ch))
-        .collect()
-}
+impl SchematicIndex {
+    fn push_number(&mut self, digits: &mut Vec<(i32, i32)>, raw: &mut usize) {
+        if digits.is_empty() {
+            return;
+        }
 
-fn get_all_neighbor_pos(center: HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
-    let mut neighbors = HashSet::new();
+        let id = self.numbers.len();
+        for &pos in digits.iter() {
+            self.number_at.insert(pos, id);
+        }
+        self.numbers.push(Number {
+            raw: *raw,
+            pos: std::mem::take(digits),
+        });
+        *raw = 0;
+    }
 
-    center.iter().for_each(|(row, col)| {
-        neighbors.insert((*row - 1, *col - 1));
-        neighbors.insert((*row - 1, *col));
-        neighbors.insert((*row - 1, *col + 1));
+    fn build(input: &str) -> SchematicIndex {
+        let mut index = SchematicIndex::default();
+
+        for (row_idx, line) in input.lines().enumerate() {
+            let mut digits = vec![];
+            let mut raw = 0;
+
+            for (col_idx, ch) in line.chars().enumerate() {
+                let pos = (row_idx as i32, col_idx as i32);
+
+                match ch.to_digit(10) {
+                    Some(digit) => {
+                        digits.push(pos);
+                        raw = raw * 10 + digit as usize;
+                    }
+                    None => {
+                        index.push_number(&mut digits, &mut raw);
+                        if ch != '.' {
+                            index.symbols.insert(pos, ch);
+                        }
+                    }
+                }
+            }
 
-        neighbors.insert((*row, *col - 1));
-        neighbors.insert((*row, *col + 1));
+            index.push_number(&mut digits, &mut raw);
+        }
 
-        neighbors.insert((*row + 1, *col - 1));
-        neighbors.insert((*row + 1, *col));
-        neighbors.insert((*row + 1, *col + 1));
-    });
+        for &pos in index.symbols.keys() {
+            let mut neighbors: Vec<_> = get_all_neighbor_pos(pos)
+                .iter()
+                .filter_map(|neighbor| index.number_at.get(neighbor).copied())
+                .collect();
+            neighbors.sort();
+            neighbors.dedup();
 
-    neighbors
-}
+            index.adjacent_numbers.insert(pos, neighbors);
+        }
 
-fn get_numbers(input: &str) -> HashSet<Number> {
-    let re = Regex::new(r"\d+").unwrap();
+        index
+    }
 
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row_idx, line)| {
-            re.find_iter(line).map(move |mtch| {
-                let pos = mtch.start();
+    fn part_numbers(&self) -> Vec<usize> {
+        let mut ids: Vec<_> = self.adjacent_numbers.values().flatten().copied().collect();
+        ids.sort();
+        ids.dedup();
 
-                Number {
-                    raw: mtch.as_str().parse().unwrap(),
-                    pos: (pos..pos + mtch.len())
-                        .map(|col_idx| (row_idx as i32, col_idx as i32))
-                        .collect(),
-                }
-            })
-        })
-        .collect()
+        ids.into_iter().map(|id| self.numbers[id].raw).collect()
+    }
+
+    fn gears(&self, symbol: char, neighbor_count: usize) -> Vec<Vec<usize>> {
+        let mut gears: Vec<_> = self
+            .symbols
+            .iter()
+            .filter(|&(_, &ch)| ch == symbol)
+            .map(|(pos, _)| (pos, &self.adjacent_numbers[pos]))
+            .filter(|(_, ids)| ids.len() == neighbor_count)
+            .map(|(pos, ids)| (pos, ids.iter().map(|&id| self.numbers[id].raw).collect()))
+            .collect();
+        gears.sort();
+
+        gears.into_iter().map(|(_, numbers)| numbers).collect()
+    }
+
+    fn symbols_adjacent_to(&self, raw: usize) -> Vec<((i32, i32), char)> {
+        let mut symbols: Vec<_> = self
+            .adjacent_numbers
+            .iter()
+            .filter(|(_, ids)| ids.iter().any(|&id| self.numbers[id].raw == raw))
+            .map(|(&pos, _)| (pos, self.symbols[&pos]))
+            .collect();
+        symbols.sort();
+
+        symbols
+    }
 }
 
 fn solve_1(input: &str) -> usize {
-    let engine_scheme = parse_input(input);
-    let all_non_numbers = get_all_non_numbers(&engine_scheme);
-    let neighbors_of_non_numbers =
-        get_all_neighbor_pos(all_non_numbers.iter().map(|(key, _)| *key).collect());
-    let numbers = get_numbers(&input);
+    SchematicIndex::build(input).part_numbers().iter().sum()
+}
 
-    numbers
+fn solve_2(input: &str) -> usize {
+    SchematicIndex::build(input)
+        .gears('*', 2)
         .iter()
-        .filter(|number| {
-            !HashSet::from_iter(number.pos.iter().cloned())
-                .intersection(&neighbors_of_non_numbers)
-                .collect::<Vec<_>>()
-                .is_empty()
-        })
-        .map(|number| number.raw)
+        .map(|numbers| numbers.iter().product::<usize>())
         .sum()
 }
 
-fn solve_2(input: &str) -> usize {
-    let engine_scheme = parse_input(input);
-    let numbers = get_numbers(&input);
-
-    let stars_only: HashMap<_, _> = engine_scheme.iter().filter(|&(_, ch)| *ch == '*').collect();
-    let mut engine_scheme_numbers_only = get_all_numbers(&engine_scheme);
-
-    let mut acc = 0;
-    for (pos, ch) in stars_only {
-        engine_scheme_numbers_only.insert(*pos, *ch);
-
-        let neighbors_of_non_numbers = get_all_neighbor_pos(HashSet::from([*pos]));
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let mut solution = Solution::from(vec![solve_1(input).to_string(), solve_2(input).to_string()]);
 
-        let neighbor_numbers = numbers.iter().filter(|number| {
-            !HashSet::from_iter(number.pos.iter().cloned())
-                .intersection(&neighbors_of_non_numbers)
-                .collect::<Vec<_>>()
-                .is_empty()
-        });
-
-        if neighbor_numbers.clone().count() != 2 {
-            continue;
+    if let Some(number) = options.parse_value("--number")? {
+        for ((row, col), symbol) in SchematicIndex::build(input).symbols_adjacent_to(number) {
+            solution.note(format!("{} at {},{}", symbol, row, col));
         }
-
-        acc += neighbor_numbers
-            .map(|number| number.raw)
-            .fold(1, |acc, x| acc * x)
     }
 
-    acc
-}
-
-pub fn solve(input: &str) {
-    println!("{}", solve_1(input));
-    println!("{}", solve_2(input));
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -161,42 +169,35 @@ mod tests {
 ...$.*....
 .664.598..";
 
-    fn get_example_hashmap() -> EngineSchema {
-        HashMap::from([
-            ((2, 0), '3'),
-            ((0, 2), '7'),
-            ((0, 1), '6'),
-            ((2, 1), '5'),
-            ((0, 0), '4'),
-            ((1, 0), '#'),
-        ])
-    }
-
     #[test]
-    fn parse_input() {
-        assert_eq!(super::parse_input(&EXAMPLE_INPUT), get_example_hashmap());
+    fn build() {
+        let index = SchematicIndex::build(EXAMPLE_INPUT);
+
+        assert_eq!(index.symbols, HashMap::from([((1, 0), '#')]));
+        assert_eq!(index.number_at[&(2, 1)], 1);
+        assert_eq!(index.adjacent_numbers[&(1, 0)], vec![0, 1]);
     }
 
     #[test]
     fn solve() {
-        assert_eq!(super::solve_1(&EXAMPLE_INPUT), 502);
+        assert_eq!(super::solve_1(EXAMPLE_INPUT), 502);
     }
 
     #[test]
     fn solve_2() {
-        assert_eq!(super::solve_2(&EXAMPLE_INPUT_2), 16345);
+        assert_eq!(super::solve_2(EXAMPLE_INPUT_2), 16345);
     }
 
     #[test]
     fn solve_2_official_example() {
-        assert_eq!(super::solve_2(&OFFICIAL_EXAMPLE_INPUT), 467835);
+        assert_eq!(super::solve_2(OFFICIAL_EXAMPLE_INPUT), 467835);
     }
 
     #[test]
     fn get_numbers() {
         assert_eq!(
-            super::get_numbers(&EXAMPLE_INPUT),
-            HashSet::from([
+            SchematicIndex::build(EXAMPLE_INPUT).numbers,
+            vec![
                 Number {
                     raw: 467,
                     pos: [(0, 0), (0, 1), (0, 2)].into()
@@ -205,7 +206,32 @@ mod tests {
                     raw: 35,
                     pos: [(2, 0), (2, 1)].into()
                 }
-            ])
+            ]
+        );
+    }
+
+    #[test]
+    fn part_numbers() {
+        assert_eq!(
+            SchematicIndex::build(OFFICIAL_EXAMPLE_INPUT).part_numbers(),
+            vec![467, 35, 633, 617, 592, 755, 664, 598]
         );
     }
+
+    #[test]
+    fn gears() {
+        let index = SchematicIndex::build(OFFICIAL_EXAMPLE_INPUT);
+
+        assert_eq!(index.gears('*', 2), vec![vec![467, 35], vec![755, 598]]);
+        assert_eq!(index.gears('*', 1), vec![vec![617]]);
+        assert_eq!(index.gears('#', 1), vec![vec![633]]);
+    }
+
+    #[test]
+    fn symbols_adjacent_to() {
+        let index = SchematicIndex::build(OFFICIAL_EXAMPLE_INPUT);
+
+        assert_eq!(index.symbols_adjacent_to(633), vec![((3, 6), '#')]);
+        assert_eq!(index.symbols_adjacent_to(114), vec![]);
+    }
 }
@@ -1,11 +1,20 @@
 use std::collections::HashSet;
 
+use crate::solver::{Options, Solution};
+
 #[derive(Debug, PartialEq)]
 struct Card {
     winning_numbers: Vec<u32>,
     guess: Vec<u32>,
 }
 
+#[derive(Debug, PartialEq)]
+struct CopyTrace {
+    winner: usize,
+    won: usize,
+    copies: usize,
+}
+
 fn parse(input: &str) -> Vec<Card> {
     input
         .lines()
@@ -35,39 +44,74 @@ fn get_count_of_matches(card: &Card) -> usize {
     winning_nos.intersection(&guess).count()
 }
 
-fn calculate_points(card: &Card) -> u32 {
-    let base: u32 = 2;
-    base.pow(get_count_of_matches(card) as u32 - 1)
+fn calculate_points(card: &Card) -> u64 {
+    match get_count_of_matches(card) {
+        0 => 0,
+        count => 2_u64.saturating_pow(count as u32 - 1),
+    }
 }
 
-fn solve_1(input: &str) -> u32 {
-    parse(input).iter().map(|card| calculate_points(card)).sum()
+fn solve_1(input: &str) -> u64 {
+    parse(input)
+        .iter()
+        .map(calculate_points)
+        .fold(0, u64::saturating_add)
 }
 
-fn solve_2(input: &str) -> usize {
-    count_of_total_cards(&parse(input)).iter().sum()
+fn solve_2(input: &str) -> Result<usize, String> {
+    count_of_total_cards(&parse(input))?
+        .into_iter()
+        .try_fold(0, usize::checked_add)
+        .ok_or("the total number of cards overflows".to_string())
 }
 
-fn count_of_total_cards(cards: &[Card]) -> Vec<usize> {
-    let mut count_of_cards = vec![1; cards.len()];
+fn count_of_total_cards(cards: &[Card]) -> Result<Vec<usize>, String> {
+    Ok(trace_copies(cards)?.0)
+}
 
-    let count_of_matches_per_card: Vec<_> = cards
-        .iter()
-        .map(|card| get_count_of_matches(card))
-        .collect();
+fn trace_copies(cards: &[Card]) -> Result<(Vec<usize>, Vec<CopyTrace>), String> {
+    let mut count_of_cards = vec![1_usize; cards.len()];
+    let mut trace = vec![];
+
+    let count_of_matches_per_card: Vec<_> = cards.iter().map(get_count_of_matches).collect();
 
     for (idx, &count) in count_of_matches_per_card.iter().enumerate() {
-        for i in 1..=count {
-            count_of_cards[idx + i] += count_of_cards[idx];
+        for won in (idx + 1..=idx + count).take_while(|&won| won < cards.len()) {
+            count_of_cards[won] = count_of_cards[won]
+                .checked_add(count_of_cards[idx])
+                .ok_or(format!("copies of card {} overflow", won + 1))?;
+            trace.push(CopyTrace {
+                winner: idx + 1,
+                won: won + 1,
+                copies: count_of_cards[idx],
+            });
         }
     }
 
-    count_of_cards
+    Ok((count_of_cards, trace))
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_1(input));
-    println!("{}", solve_2(input));
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let mut solution = Solution::from(vec![
+        solve_1(input).to_string(),
+        solve_2(input)?.to_string(),
+    ]);
+
+    if options.flag("--trace") {
+        for CopyTrace {
+            winner,
+            won,
+            copies,
+        } in trace_copies(&parse(input))?.1
+        {
+            solution.note(format!(
+                "Card {} won {} copies of card {}",
+                winner, copies, won
+            ));
+        }
+    }
+
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -125,11 +169,62 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         assert_eq!(super::calculate_points(&get_example_cards()[0]), 8);
     }
 
+    #[test]
+    fn calculate_points_without_matches() {
+        assert_eq!(super::calculate_points(&get_example_cards()[4]), 0);
+    }
+
+    #[test]
+    fn solve_1_saturates() {
+        let numbers: Vec<_> = (1..=64).map(|number| number.to_string()).collect();
+        let card = format!("Card 1: {0} | {0}", numbers.join(" "));
+
+        assert_eq!(super::solve_1(&format!("{}\n{}", card, card)), u64::MAX);
+    }
+
+    #[test]
+    fn count_of_total_cards_at_end_of_table() {
+        let cards = super::parse("Card 1: 1 2 | 1 2\nCard 2: 3 4 | 3 4");
+        assert_eq!(super::count_of_total_cards(&cards), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn trace_copies() {
+        let (_, trace) = super::trace_copies(&get_example_cards()[2..]).unwrap();
+        assert_eq!(
+            trace,
+            vec![
+                CopyTrace {
+                    winner: 1,
+                    won: 2,
+                    copies: 1
+                },
+                CopyTrace {
+                    winner: 1,
+                    won: 3,
+                    copies: 1
+                },
+                CopyTrace {
+                    winner: 2,
+                    won: 3,
+                    copies: 2
+                },
+            ]
+        );
+    }
+
     #[test]
     fn count_of_total_cards() {
         assert_eq!(
             super::count_of_total_cards(&get_example_cards()),
-            [1, 2, 4, 8, 14, 1]
+            Ok(vec![1, 2, 4, 8, 14, 1])
         );
     }
+
+    #[test]
+    fn solve_2_overflows() {
+        let card = "Card 1: 1 2 3 4 5 6 7 8 9 10 | 1 2 3 4 5 6 7 8 9 10";
+
+        assert!(super::solve_2(&vec![card; 100].join("\n")).is_err());
+    }
 }
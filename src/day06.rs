@@ -1,15 +1,69 @@
+use crate::solver::{Options, Solution};
+
 fn calculate_possibilities(time: u64, distance: u64) -> usize {
     (1..time).filter(|i| i * (time - i) > distance).count()
 }
 
-pub fn solve(input: &str) {
-    println!(
-        "{}",
-        calculate_possibilities(51, 222)
-            * calculate_possibilities(92, 2031)
-            * calculate_possibilities(68, 1126)
-            * calculate_possibilities(90, 1225)
-    );
+fn parse_line(line: &str) -> Vec<u64> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|number| number.parse().unwrap())
+        .collect()
+}
+
+fn parse(input: &str) -> Vec<(u64, u64)> {
+    let (times, distances) = input.split_once('\n').unwrap();
+    parse_line(times)
+        .into_iter()
+        .zip(parse_line(distances))
+        .collect()
+}
+
+fn parse_joined(input: &str) -> (u64, u64) {
+    let joined = input.replace(' ', "").replace(':', ": ");
+    parse(&joined)[0]
+}
+
+fn solve_1(races: &[(u64, u64)]) -> usize {
+    races
+        .iter()
+        .map(|&(time, distance)| calculate_possibilities(time, distance))
+        .product()
+}
+
+fn solve_2((time, distance): (u64, u64)) -> usize {
+    calculate_possibilities(time, distance)
+}
+
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![
+        solve_1(&parse(input)).to_string(),
+        solve_2(parse_joined(input)).to_string(),
+    ]
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse(EXAMPLE_INPUT),
+            vec![(7, 9), (15, 40), (30, 200)]
+        );
+        assert_eq!(super::parse_joined(EXAMPLE_INPUT), (71530, 940200));
+    }
+
+    #[test]
+    fn solve_1() {
+        assert_eq!(super::solve_1(&super::parse(EXAMPLE_INPUT)), 288);
+    }
 
-    println!("{}", calculate_possibilities(51926890, 222203111261225));
+    #[test]
+    fn solve_2() {
+        assert_eq!(super::solve_2(super::parse_joined(EXAMPLE_INPUT)), 71503);
+    }
 }
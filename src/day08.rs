@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 extern crate num_integer;
 
+use crate::solver::{Options, Solution};
+
 type Network = HashMap<String, (String, String)>;
 
 fn parse_into_network(input: &str) -> Network {
@@ -57,7 +59,7 @@ fn lcm(a: usize, b: usize) -> usize {
 fn solve_2(network: &Network, directions: Vec<char>) -> usize {
     let mut factors = Vec::new();
 
-    for node in get_all_nodes_ending_with_a(&network) {
+    for node in get_all_nodes_ending_with_a(network) {
         let mut current_node_name = node;
         let mut ctr = 0;
 
@@ -82,17 +84,14 @@ fn solve_2(network: &Network, directions: Vec<char>) -> usize {
     least_common_multiplier
 }
 
-pub fn solve(input: &str) {
-    const DIRECTIONS : &str = "LRLRRRLRRLRRRLRRRLLLLLRRRLRLRRLRLRLRRLRRLRRRLRLRLRRLLRLRRLRRLRRLRRRLLRRRLRRRLRRLRLLLRRLRRRLRLRRLRRRLRRLRLLLRRRLRRLRRLRRRLRRRLRRRLRLRLRLRRRLRRRLLLRRLLRRRLRLRLRRRLRRRLRRLRRRLRLRLLRRRLRLRRLRLRLRRLLLRRRLRRRLRRLRRLRLRRLLRRLRRRLRRRLLRRRLRRLRLLRRLRLRRLLRRRLLLLRRLRRRLRLRRLLRLLRRRLLRRLLRRRLRRRLRRLLRLRLLRRLLRLLLRRRR";
-    println!(
-        "{}",
-        solve_1(parse_into_network(&input), parse_directions(DIRECTIONS))
-    );
-
-    println!(
-        "{}",
-        solve_2(&parse_into_network(&input), parse_directions(DIRECTIONS))
-    );
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    let (directions, network) = input.split_once("\n\n").unwrap();
+
+    Ok(vec![
+        solve_1(parse_into_network(network), parse_directions(directions)).to_string(),
+        solve_2(&parse_into_network(network), parse_directions(directions)).to_string(),
+    ]
+    .into())
 }
 
 #[cfg(test)]
@@ -124,10 +123,10 @@ ZZZ = (ZZZ, ZZZ)";
     #[test]
     fn parse() {
         assert_eq!(
-            parse_into_network(&EXAMPLE_NETWORK),
+            parse_into_network(EXAMPLE_NETWORK),
             get_example_network_as_nodes()
         );
-        assert_eq!(super::parse_directions(&EXAMPLE_DIRECTIONS), vec!['R', 'L']);
+        assert_eq!(super::parse_directions(EXAMPLE_DIRECTIONS), vec!['R', 'L']);
     }
 
     #[test]
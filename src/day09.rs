@@ -1,3 +1,45 @@
+use std::fmt;
+
+use crate::solver::{Options, Solution};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Polynomial {
+    differences: Vec<i128>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum AnalysisError {
+    Empty,
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnalysisError::Empty => write!(f, "empty sequence"),
+        }
+    }
+}
+
+impl Polynomial {
+    fn degree(&self) -> usize {
+        self.differences.len() - 1
+    }
+
+    fn value_at(&self, index: i128) -> i128 {
+        let mut binomial = 1;
+        let mut value = 0;
+
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                binomial = binomial * (index - k as i128 + 1) / k as i128;
+            }
+            value += binomial * difference;
+        }
+
+        value
+    }
+}
+
 fn parse(input: &str) -> Vec<Vec<i64>> {
     input
         .lines()
@@ -9,46 +51,85 @@ fn parse(input: &str) -> Vec<Vec<i64>> {
         .collect()
 }
 
-fn extrapolate_end(report: &[i64]) -> i64 {
-    if report.iter().any(|&a| a != 0) {
-        let differences: Vec<i64> = report
-            .windows(2)
-            .map(|window| window[1] - window[0])
-            .collect();
-        return extrapolate_end(&differences) + report.last().unwrap();
-    } else {
-        return 0;
+fn analyze(report: &[i64]) -> Result<Polynomial, AnalysisError> {
+    if report.is_empty() {
+        return Err(AnalysisError::Empty);
     }
-}
 
-fn extrapolate_begin(report: &[i64]) -> i64 {
-    if report.iter().any(|&a| a != 0) {
-        let differences: Vec<i64> = report
-            .windows(2)
-            .map(|window| window[1] - window[0])
-            .collect();
-        return report.first().unwrap() - extrapolate_begin(&differences);
-    } else {
-        return 0;
+    let mut row: Vec<i128> = report.iter().map(|&value| value as i128).collect();
+    let mut differences = vec![];
+
+    // n values whose differences never become constant, e.g. `0 1` or
+    // `1 2 4 8`, are fitted with the polynomial of degree n - 1 through them
+    loop {
+        differences.push(row[0]);
+        if row.windows(2).all(|window| window[0] == window[1]) {
+            return Ok(Polynomial { differences });
+        }
+
+        row = row.windows(2).map(|window| window[1] - window[0]).collect();
     }
 }
 
-fn solve_for(input: &str, extrapolation: fn(&[i64]) -> i64) -> i64 {
+fn extrapolate_end(report: &[i64]) -> Result<i128, AnalysisError> {
+    analyze(report).map(|polynomial| polynomial.value_at(report.len() as i128))
+}
+
+fn extrapolate_begin(report: &[i64]) -> Result<i128, AnalysisError> {
+    analyze(report).map(|polynomial| polynomial.value_at(-1))
+}
+
+fn extrapolate_by(report: &[i64], offset: i128) -> Result<i128, AnalysisError> {
+    analyze(report).map(|polynomial| polynomial.value_at(report.len() as i128 - 1 + offset))
+}
+
+fn solve_for(
+    input: &str,
+    extrapolation: impl Fn(&[i64]) -> Result<i128, AnalysisError>,
+) -> Result<i128, AnalysisError> {
     parse(input)
         .iter()
         .map(|report| extrapolation(report))
         .sum()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_for(&input, extrapolate_end));
-    println!("{}", solve_for(&input, extrapolate_begin));
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let mut solution = Solution::from(vec![
+        solve_for(input, extrapolate_end)
+            .map_err(|error| error.to_string())?
+            .to_string(),
+        solve_for(input, extrapolate_begin)
+            .map_err(|error| error.to_string())?
+            .to_string(),
+    ]);
+
+    if let Some(offset) = options.parse_value::<i128>("--offset")? {
+        match solve_for(input, |report| extrapolate_by(report, offset)) {
+            Ok(value) => solution.note(format!("offset {}: {}", offset, value)),
+            Err(error) => solution.note(error.to_string()),
+        }
+    }
+
+    if options.flag("--degrees") {
+        for report in parse(input) {
+            match analyze(&report) {
+                Ok(polynomial) => solution.note(format!("degree {}", polynomial.degree())),
+                Err(error) => solution.note(error.to_string()),
+            }
+        }
+    }
+
+    Ok(solution)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
     #[test]
     fn parse() {
         assert_eq!(
@@ -59,6 +140,53 @@ mod tests {
 
     #[test]
     fn extrapolate() {
-        assert_eq!(super::extrapolate_end(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(super::extrapolate_end(&[0, 3, 6, 9, 12, 15]), Ok(18));
+    }
+
+    #[test]
+    fn solve_for() {
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, extrapolate_end), Ok(114));
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, extrapolate_begin), Ok(2));
+    }
+
+    #[test]
+    fn analyze() {
+        assert_eq!(
+            super::analyze(&[10, 13, 16, 21, 30, 45]).unwrap().degree(),
+            3
+        );
+        assert_eq!(super::analyze(&[7, 7]).unwrap().degree(), 0);
+        assert_eq!(super::analyze(&[7]).unwrap().degree(), 0);
+        assert_eq!(super::extrapolate_end(&[7]), Ok(7));
+        assert_eq!(super::extrapolate_begin(&[7]), Ok(7));
+        assert_eq!(super::analyze(&[]), Err(AnalysisError::Empty));
+        assert_eq!(super::analyze(&[1, 2, 4, 8, 16]).unwrap().degree(), 4);
+        assert_eq!(super::extrapolate_end(&[1, 2, 4, 8, 16]), Ok(31));
+    }
+
+    #[test]
+    fn analyze_two_values() {
+        assert_eq!(super::analyze(&[0, 1]).unwrap().degree(), 1);
+        assert_eq!(super::extrapolate_end(&[0, 1]), Ok(2));
+        assert_eq!(super::extrapolate_begin(&[0, 1]), Ok(-1));
+        assert_eq!(
+            super::solve_for("0 1\n0 3 6 9 12 15", extrapolate_end),
+            Ok(20)
+        );
+    }
+
+    #[test]
+    fn value_at_arbitrary_offsets() {
+        let squares = super::analyze(&[0, 1, 4, 9]).unwrap();
+
+        assert_eq!(squares.value_at(1000), 1000000);
+        assert_eq!(squares.value_at(-50), 2500);
+        assert_eq!(super::extrapolate_by(&[0, 1, 4, 9], 1000), Ok(1003 * 1003));
+        assert_eq!(
+            super::analyze(&[10, 13, 16, 21, 30, 45])
+                .unwrap()
+                .value_at(-1),
+            5
+        );
     }
 }
@@ -1,4 +1,7 @@
 use itertools::Itertools;
+use std::collections::BTreeSet;
+
+use crate::solver::{Options, Solution};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Tile {
@@ -57,52 +60,193 @@ fn get_star_coordinates(universe: &Universe) -> Vec<(usize, usize)> {
         .collect()
 }
 
-fn get_manhattan_distance(
-    star_1: (usize, usize),
-    star_2: (usize, usize),
-    dark_matter: &DarkMatter,
-    expansion_rate: usize,
-) -> usize {
-    let min_row = star_1.0.min(star_2.0);
-    let max_row = star_1.0.max(star_2.0);
-    let min_col = star_1.1.min(star_2.1);
-    let max_col = star_1.1.max(star_2.1);
-
-    let expanded_rows = dark_matter
-        .rows
+type StarPair = (usize, (usize, usize), (usize, usize));
+
+fn get_expanded_coordinates(
+    universe: &Universe,
+    row_expansion: usize,
+    col_expansion: usize,
+) -> Vec<(usize, usize)> {
+    let dark_matter = get_dark_matter(universe);
+
+    get_star_coordinates(universe)
+        .into_iter()
+        .map(|(row, col)| {
+            let expanded_rows = dark_matter.rows.partition_point(|&dark_row| dark_row < row);
+            let expanded_cols = dark_matter
+                .columns
+                .partition_point(|&dark_col| dark_col < col);
+
+            (
+                row - expanded_rows + expanded_rows * row_expansion,
+                col - expanded_cols + expanded_cols * col_expansion,
+            )
+        })
+        .collect()
+}
+
+fn sum_pairwise_distances(mut coordinates: Vec<usize>) -> usize {
+    coordinates.sort();
+
+    let mut prefix_sum = 0;
+    coordinates
         .iter()
-        .filter(|row| (min_row..max_row).contains(row))
-        .count();
-    let expanded_cols = dark_matter
-        .columns
+        .enumerate()
+        .map(|(idx, &coordinate)| {
+            let distance = coordinate * idx - prefix_sum;
+            prefix_sum += coordinate;
+            distance
+        })
+        .sum()
+}
+
+fn get_manhattan_distance(star_1: (usize, usize), star_2: (usize, usize)) -> usize {
+    star_1.0.abs_diff(star_2.0) + star_1.1.abs_diff(star_2.1)
+}
+
+fn get_farthest_pair(stars: &[(usize, usize)], expanded: &[(usize, usize)]) -> Option<StarPair> {
+    let rotated: Vec<_> = expanded
         .iter()
-        .filter(|col| (min_col..max_col).contains(col))
-        .count();
+        .map(|&(row, col)| (row as i64 + col as i64, row as i64 - col as i64))
+        .collect();
 
-    max_row - min_row + max_col - min_col + (expanded_rows + expanded_cols) * (expansion_rate - 1)
+    let extremes = |key: fn(&(i64, i64)) -> i64| {
+        let min = (0..rotated.len()).min_by_key(|&idx| key(&rotated[idx]))?;
+        let max = (0..rotated.len()).max_by_key(|&idx| key(&rotated[idx]))?;
+        Some((min, max))
+    };
+
+    [extremes(|&(sum, _)| sum)?, extremes(|&(_, diff)| diff)?]
+        .into_iter()
+        .map(|(a, b)| {
+            (
+                get_manhattan_distance(expanded[a], expanded[b]),
+                stars[a],
+                stars[b],
+            )
+        })
+        .max_by_key(|&(distance, _, _)| distance)
 }
 
-fn solve_for(input: &str, expansion_rate: usize) -> usize {
-    let universe = parse_input(input);
+fn get_closest_pair(stars: &[(usize, usize)], expanded: &[(usize, usize)]) -> Option<StarPair> {
+    let mut order: Vec<_> = (0..stars.len()).collect();
+    order.sort_by_key(|&idx| expanded[idx]);
+
+    let mut closest: Option<StarPair> = None;
+    let mut active = BTreeSet::new();
+    let mut oldest = 0;
+
+    for &idx in &order {
+        let (row, col) = expanded[idx];
+
+        if let Some((best, _, _)) = closest {
+            while expanded[order[oldest]].0 + best < row {
+                let (old_row, old_col) = expanded[order[oldest]];
+                active.remove(&(old_col, old_row, order[oldest]));
+                oldest += 1;
+            }
+        }
+
+        let window = closest.map_or(usize::MAX, |(best, _, _)| best);
+        let candidates = active.range(
+            (col.saturating_sub(window), 0, 0)
+                ..=(col.saturating_add(window), usize::MAX, usize::MAX),
+        );
+        for &(other_col, other_row, other) in candidates {
+            let distance = get_manhattan_distance((row, col), (other_row, other_col));
+            if closest.is_none_or(|(best, _, _)| distance < best) {
+                closest = Some((distance, stars[other], stars[idx]));
+            }
+        }
+
+        active.insert((col, row, idx));
+    }
+
+    closest
+}
 
-    let dark_matter = get_dark_matter(&universe);
-    let stars = get_star_coordinates(&universe);
+fn solve_for(input: &str, row_expansion: usize, col_expansion: usize) -> usize {
+    let universe = parse_input(input);
+    let expanded = get_expanded_coordinates(&universe, row_expansion, col_expansion);
 
-    stars
-        .iter()
-        .tuple_combinations()
-        .map(|(&star_1, &star_2)| {
-            get_manhattan_distance(star_1, star_2, &dark_matter, expansion_rate)
-        })
-        .sum()
+    sum_pairwise_distances(expanded.iter().map(|&(row, _)| row).collect())
+        + sum_pairwise_distances(expanded.iter().map(|&(_, col)| col).collect())
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_for(&input, 2));
-    println!("{}", solve_for(&input, 1000000));
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let mut solution = Solution::from(vec![
+        solve_for(input, 2, 2).to_string(),
+        solve_for(input, 1000000, 1000000).to_string(),
+    ]);
+
+    if options.flag("--pairs") {
+        let universe = parse_input(input);
+        let stars = get_star_coordinates(&universe);
+        let expanded = get_expanded_coordinates(&universe, 1000000, 1000000);
+
+        for (name, pair) in [
+            ("closest", get_closest_pair(&stars, &expanded)),
+            ("farthest", get_farthest_pair(&stars, &expanded)),
+        ] {
+            if let Some((distance, star_1, star_2)) = pair {
+                solution.note(format!("{}: {:?} {:?} {}", name, star_1, star_2, distance));
+            }
+        }
+    }
+
+    Ok(solution)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const EXAMPLE_INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn solve_for() {
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, 2, 2), 374);
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, 10, 10), 1030);
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, 100, 100), 8410);
+    }
+
+    #[test]
+    fn solve_for_distinct_expansions() {
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, 1, 1), 292);
+        assert_eq!(super::solve_for(EXAMPLE_INPUT, 0, 0), 210);
+        assert_eq!(
+            super::solve_for(EXAMPLE_INPUT, 2, 1) + super::solve_for(EXAMPLE_INPUT, 1, 2),
+            374 + 292
+        );
+    }
+
+    #[test]
+    fn sum_pairwise_distances() {
+        assert_eq!(super::sum_pairwise_distances(vec![5, 1, 3]), 8);
+    }
+
+    #[test]
+    fn get_closest_and_farthest_pair() {
+        let universe = parse_input(EXAMPLE_INPUT);
+        let stars = get_star_coordinates(&universe);
+        let expanded = get_expanded_coordinates(&universe, 2, 2);
+
+        assert_eq!(
+            get_closest_pair(&stars, &expanded).map(|(distance, _, _)| distance),
+            Some(5)
+        );
+        assert_eq!(
+            get_farthest_pair(&stars, &expanded),
+            Some((19, (1, 7), (9, 0)))
+        );
+    }
 }
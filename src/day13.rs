@@ -1,80 +1,143 @@
-fn get_vertical_symmetrical_index(input: &str, discrepancy: usize) -> Option<usize> {
-    let col_cnt = input.lines().next().unwrap().len();
-
-    (0..col_cnt - 1)
-        .find(|&i| {
-            (0..std::cmp::min(i + 1, col_cnt - i - 1))
-                .map(|offset_from_mirror| {
-                    input
-                        .lines()
-                        .map(|line| {
-                            (line.as_bytes()[i - offset_from_mirror]
-                                != line.as_bytes()[i + 1 + offset_from_mirror])
-                                as usize
-                        })
-                        .sum::<usize>()
-                })
-                .sum::<usize>()
-                == discrepancy
-        })
-        .map(|x| x + 1)
+use crate::solver::{Options, Solution};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn score(&self) -> usize {
+        match self {
+            Reflection::Vertical(columns) => *columns,
+            Reflection::Horizontal(rows) => rows * 100,
+        }
+    }
+}
+
+fn parse_pattern(input: &str) -> Result<Pattern, String> {
+    let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let width = grid.first().map_or(0, |row| row.len());
+    if width > 64 || grid.len() > 64 || grid.iter().any(|row| row.len() != width) {
+        return Err("pattern must be a rectangle of at most 64x64 cells".to_string());
+    }
+
+    let to_mask = |cells: &mut dyn Iterator<Item = u8>| {
+        cells
+            .enumerate()
+            .filter(|&(_, cell)| cell == b'#')
+            .fold(0, |mask, (idx, _)| mask | 1 << idx)
+    };
+
+    Ok(Pattern {
+        rows: grid
+            .iter()
+            .map(|row| to_mask(&mut row.iter().copied()))
+            .collect(),
+        columns: (0..width)
+            .map(|col_idx| to_mask(&mut grid.iter().map(|row| row[col_idx])))
+            .collect(),
+    })
 }
 
-fn get_horizontal_symmetrical_index(input: &str, discrepancy: usize) -> Option<usize> {
-    let row_cnt = input.lines().count();
-    let col_cnt = input.lines().next().unwrap().len();
-
-    (0..row_cnt - 1)
-        .find(|&i| {
-            (0..std::cmp::min(i + 1, row_cnt - i - 1))
-                .map(|offset_from_mirror| {
-                    (0..col_cnt)
-                        .filter(|col_idx| {
-                            input
-                                .lines()
-                                .nth(i - offset_from_mirror)
-                                .unwrap()
-                                .as_bytes()[*col_idx]
-                                != input
-                                    .lines()
-                                    .nth(i + 1 + offset_from_mirror)
-                                    .unwrap()
-                                    .as_bytes()[*col_idx]
-                        })
-                        .count()
-                })
-                .sum::<usize>()
+fn get_mirror_pairs(len: usize, mirror: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..mirror.min(len - mirror)).map(move |offset| (mirror - 1 - offset, mirror + offset))
+}
+
+fn find_mirrors(lines: &[u64], discrepancy: u32) -> Vec<usize> {
+    (1..lines.len())
+        .filter(|&mirror| {
+            get_mirror_pairs(lines.len(), mirror)
+                .map(|(a, b)| (lines[a] ^ lines[b]).count_ones())
+                .sum::<u32>()
                 == discrepancy
         })
-        .map(|x| x + 1)
+        .collect()
 }
 
-fn summarize(puzzle: &str, discrepancy: usize) -> usize {
-    get_vertical_symmetrical_index(&puzzle, discrepancy)
-        .or_else(|| get_horizontal_symmetrical_index(&puzzle, discrepancy).map(|x| x * 100))
-        .unwrap_or(0)
+fn get_reflections(pattern: &Pattern, discrepancy: u32) -> Vec<Reflection> {
+    find_mirrors(&pattern.columns, discrepancy)
+        .into_iter()
+        .map(Reflection::Vertical)
+        .chain(
+            find_mirrors(&pattern.rows, discrepancy)
+                .into_iter()
+                .map(Reflection::Horizontal),
+        )
+        .collect()
 }
 
-pub fn solve(input: &str) {
-    println!(
-        "{}",
-        input
-            .split("\n\n")
-            .map(|puzzle| summarize(puzzle, 0))
-            .sum::<usize>()
-    );
+fn find_smudge(pattern: &Pattern) -> Option<((usize, usize), Reflection)> {
+    let reflection = *get_reflections(pattern, 1).first()?;
 
-    println!(
-        "{}",
-        input
-            .split("\n\n")
-            .map(|puzzle| summarize(puzzle, 1))
-            .sum::<usize>()
+    let (lines, mirror) = match reflection {
+        Reflection::Vertical(mirror) => (&pattern.columns, mirror),
+        Reflection::Horizontal(mirror) => (&pattern.rows, mirror),
+    };
+    let (line, bit) = get_mirror_pairs(lines.len(), mirror).find_map(|(a, b)| {
+        let difference = lines[a] ^ lines[b];
+        (difference != 0).then(|| (a, difference.trailing_zeros() as usize))
+    })?;
+
+    let smudge = match reflection {
+        Reflection::Vertical(_) => (bit, line),
+        Reflection::Horizontal(_) => (line, bit),
+    };
+    Some((smudge, reflection))
+}
+
+fn summarize(pattern: &Pattern, discrepancy: u32) -> usize {
+    get_reflections(pattern, discrepancy)
+        .first()
+        .map_or(0, Reflection::score)
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let patterns = input
+        .split("\n\n")
+        .map(parse_pattern)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut solution = Solution::from(
+        [0, 1]
+            .into_iter()
+            .map(|smudges| {
+                patterns
+                    .iter()
+                    .map(|pattern| summarize(pattern, smudges))
+                    .sum::<usize>()
+                    .to_string()
+            })
+            .collect::<Vec<_>>(),
     );
+
+    if options.flag("--smudges") {
+        for (idx, pattern) in patterns.iter().enumerate() {
+            if let Some(((row, col), reflection)) = find_smudge(pattern) {
+                solution.note(format!(
+                    "pattern {}: smudge at {},{} -> {:?}, all reflections {:?}",
+                    idx + 1,
+                    row,
+                    col,
+                    reflection,
+                    get_reflections(pattern, 0)
+                ));
+            }
+        }
+    }
+
+    Ok(solution)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     const VERTICAL_SYM_INPUT: &str = "#.##..##.
 ..#.##.#.
@@ -94,16 +157,61 @@ mod tests {
 
     #[test]
     fn vertical_symmetry() {
-        assert_eq!(super::summarize(&VERTICAL_SYM_INPUT, 0), 5);
+        assert_eq!(
+            super::summarize(&super::parse_pattern(VERTICAL_SYM_INPUT).unwrap(), 0),
+            5
+        );
     }
 
     #[test]
     fn summarize_wo_discrepancy() {
-        assert_eq!(super::summarize(&HORIZONTAL_SYM_INPUT, 0), 400);
+        assert_eq!(
+            super::summarize(&super::parse_pattern(HORIZONTAL_SYM_INPUT).unwrap(), 0),
+            400
+        );
     }
 
     #[test]
     fn summarize_with_discrepancy() {
-        assert_eq!(super::summarize(&VERTICAL_SYM_INPUT, 1), 300);
+        assert_eq!(
+            super::summarize(&super::parse_pattern(VERTICAL_SYM_INPUT).unwrap(), 1),
+            300
+        );
+    }
+
+    #[test]
+    fn parse_pattern() {
+        let pattern = super::parse_pattern("#.\n.#\n##").unwrap();
+
+        assert_eq!(pattern.rows, vec![0b01, 0b10, 0b11]);
+        assert_eq!(pattern.columns, vec![0b101, 0b110]);
+        assert!(super::parse_pattern(&"#".repeat(64)).is_ok());
+        assert!(super::parse_pattern(&"#".repeat(65)).is_err());
+        assert!(super::parse_pattern(&vec!["#"; 65].join("\n")).is_err());
+        assert!(super::parse_pattern("##\n#").is_err());
+    }
+
+    #[test]
+    fn get_reflections() {
+        assert_eq!(
+            super::get_reflections(&super::parse_pattern(HORIZONTAL_SYM_INPUT).unwrap(), 0),
+            vec![Reflection::Horizontal(4)]
+        );
+        assert_eq!(
+            super::get_reflections(&super::parse_pattern("##\n##").unwrap(), 0),
+            vec![Reflection::Vertical(1), Reflection::Horizontal(1)]
+        );
+    }
+
+    #[test]
+    fn find_smudge() {
+        assert_eq!(
+            super::find_smudge(&super::parse_pattern(VERTICAL_SYM_INPUT).unwrap()),
+            Some(((0, 0), Reflection::Horizontal(3)))
+        );
+        assert_eq!(
+            super::find_smudge(&super::parse_pattern(HORIZONTAL_SYM_INPUT).unwrap()),
+            Some(((0, 4), Reflection::Horizontal(1)))
+        );
     }
 }
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use crate::solver::{Options, Solution};
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 enum Tile {
     Empty,
@@ -5,6 +9,7 @@ enum Tile {
     Rock,
 }
 
+#[derive(Clone, Copy)]
 enum Direction {
     North,
     South,
@@ -30,54 +35,131 @@ fn get_total_load(platform: &Platform) -> usize {
         .sum()
 }
 
-fn tilt(platform: &Platform, direction: Direction) -> Platform {
-    let mut tilted_platform = platform.clone();
-
-    let move_vector: (i32, i32) = match direction {
-        Direction::North => (0, -1),
-        Direction::South => (0, 1),
-        Direction::East => (1, 0),
-        Direction::West => (-1, 0),
-    };
-
-    let width = platform.first().unwrap().len();
-    let height = platform.len();
-
-    for y in 0..height {
-        for x in 0..width {
-            if tilted_platform[y][x] == Tile::Rock {
-                let mut last_x = x;
-                let mut last_y = y;
-
-                for i in 1.. {
-                    let new_x = x as i32 + i * move_vector.0;
-                    let new_y = y as i32 + i * move_vector.1;
-
-                    if new_x < 0 || new_x >= width as i32 || new_y < 0 || new_y >= height as i32 {
-                        if last_x != x || last_y != y {
-                            tilted_platform[last_y][last_x] = Tile::Rock;
-                            tilted_platform[y][x] = Tile::Empty;
-                        }
-                        break;
-                    }
-
-                    if tilted_platform[new_y as usize][new_x as usize] == Tile::Empty {
-                        last_x = new_x as usize;
-                        last_y = new_y as usize;
-                        continue;
-                    } else {
-                        if last_x != x || last_y != y {
-                            tilted_platform[last_y][last_x] = Tile::Rock;
-                            tilted_platform[y][x] = Tile::Empty;
-                        }
-                        break;
-                    }
-                }
+#[derive(Debug)]
+struct Dish {
+    width: usize,
+    height: usize,
+    walls: Vec<bool>,
+    rocks: Vec<bool>,
+    segments: [Vec<Vec<usize>>; 4],
+}
+
+impl Direction {
+    fn index(&self) -> usize {
+        match self {
+            Direction::North => 0,
+            Direction::West => 1,
+            Direction::South => 2,
+            Direction::East => 3,
+        }
+    }
+}
+
+fn build_segments(lines: &[Vec<usize>], walls: &[bool]) -> Vec<Vec<usize>> {
+    lines
+        .iter()
+        .flat_map(|line| line.split(|&cell| walls[cell]))
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_vec())
+        .collect()
+}
+
+impl Dish {
+    fn new(platform: &Platform) -> Dish {
+        let height = platform.len();
+        let width = platform.first().map_or(0, |row| row.len());
+        let tiles: Vec<_> = platform.iter().flatten().collect();
+        let walls: Vec<_> = tiles.iter().map(|&&tile| tile == Tile::Wall).collect();
+
+        let columns: Vec<Vec<_>> = (0..width)
+            .map(|x| (0..height).map(|y| y * width + x).collect())
+            .collect();
+        let rows: Vec<Vec<_>> = (0..height)
+            .map(|y| (0..width).map(|x| y * width + x).collect())
+            .collect();
+        let reversed = |lines: &[Vec<usize>]| -> Vec<Vec<usize>> {
+            lines
+                .iter()
+                .map(|line| line.iter().rev().copied().collect())
+                .collect()
+        };
+
+        Dish {
+            width,
+            height,
+            rocks: tiles.iter().map(|&&tile| tile == Tile::Rock).collect(),
+            segments: [
+                build_segments(&columns, &walls),
+                build_segments(&rows, &walls),
+                build_segments(&reversed(&columns), &walls),
+                build_segments(&reversed(&rows), &walls),
+            ],
+            walls,
+        }
+    }
+
+    fn tilt(&mut self, direction: Direction) {
+        for segment in &self.segments[direction.index()] {
+            let rock_count = segment.iter().filter(|&&cell| self.rocks[cell]).count();
+            for (idx, &cell) in segment.iter().enumerate() {
+                self.rocks[cell] = idx < rock_count;
             }
         }
     }
 
-    tilted_platform
+    fn spin_cycle(&mut self) {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    fn state(&self) -> Vec<u64> {
+        self.rocks
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &rock)| rock)
+                    .fold(0, |bits, (idx, _)| bits | 1 << idx)
+            })
+            .collect()
+    }
+
+    fn total_load(&self) -> usize {
+        self.rocks
+            .iter()
+            .enumerate()
+            .filter(|(_, &rock)| rock)
+            .map(|(cell, _)| self.height - cell / self.width)
+            .sum()
+    }
+
+    fn to_platform(&self) -> Platform {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| y * self.width + x)
+                    .map(|cell| match (self.walls[cell], self.rocks[cell]) {
+                        (true, _) => Tile::Wall,
+                        (_, true) => Tile::Rock,
+                        _ => Tile::Empty,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn tilt(platform: &Platform, direction: Direction) -> Platform {
+    let mut dish = Dish::new(platform);
+    dish.tilt(direction);
+    dish.to_platform()
 }
 
 fn parse_into_platform(input: &str) -> Platform {
@@ -101,38 +183,33 @@ fn parse_into_platform(input: &str) -> Platform {
 }
 
 fn solve_1(input: &str) -> usize {
-    get_total_load(&tilt(&parse_into_platform(&input), Direction::North))
+    get_total_load(&tilt(&parse_into_platform(input), Direction::North))
 }
 
-fn tilt_one_cycle(platform: &Platform) -> Platform {
-    let mut initial_tilt = platform.clone();
-    initial_tilt = tilt(&initial_tilt, Direction::North);
-    initial_tilt = tilt(&initial_tilt, Direction::West);
-    initial_tilt = tilt(&initial_tilt, Direction::South);
-    initial_tilt = tilt(&initial_tilt, Direction::East);
-    initial_tilt
-}
+fn get_load_after_cycles(platform: &Platform, cycles: usize) -> usize {
+    let mut dish = Dish::new(platform);
+    let mut seen = HashMap::new();
+    let mut loads = vec![];
 
-fn solve_2(input: &str) -> usize {
-    let mut initial_tilt = parse_into_platform(&input);
-    let mut last_platform: Platform = initial_tilt.clone();
-
-    for _ in 1..1000000000 {
-        initial_tilt = tilt_one_cycle(&initial_tilt);
-
-        if last_platform != initial_tilt {
-            last_platform = initial_tilt.clone();
-        } else {
-            break;
+    for cycle in 0..cycles {
+        if let Some(&start) = seen.get(&dish.state()) {
+            return loads[start + (cycles - start) % (cycle - start)];
         }
+
+        seen.insert(dish.state(), cycle);
+        loads.push(dish.total_load());
+        dish.spin_cycle();
     }
 
-    get_total_load(&initial_tilt)
+    dish.total_load()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_1(input));
-    println!("{}", solve_2(input));
+fn solve_2(input: &str) -> usize {
+    get_load_after_cycles(&parse_into_platform(input), 1000000000)
+}
+
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(input).to_string(), solve_2(input).to_string()].into())
 }
 
 #[cfg(test)]
@@ -214,18 +291,18 @@ O..#.OO...
 
     #[test]
     fn solve_1() {
-        assert_eq!(super::solve_1(&EXAMPLE_INPUT), 136);
+        assert_eq!(super::solve_1(EXAMPLE_INPUT), 136);
     }
 
     #[test]
     fn solve_2() {
-        assert_eq!(super::solve_2(&EXAMPLE_INPUT), 64);
+        assert_eq!(super::solve_2(EXAMPLE_INPUT), 64);
     }
 
     #[test]
     fn parse_into_platform() {
         assert_eq!(
-            super::parse_into_platform(&SMALL_INPUT),
+            super::parse_into_platform(SMALL_INPUT),
             get_parsed_small_input()
         );
     }
@@ -234,7 +311,7 @@ O..#.OO...
     fn tilt_small_example() {
         assert_eq!(
             super::tilt(
-                &super::parse_into_platform(&SMALL_INPUT_2),
+                &super::parse_into_platform(SMALL_INPUT_2),
                 Direction::North
             ),
             get_parsed_small_input_2_after_tilt()
@@ -245,21 +322,42 @@ O..#.OO...
     fn tilt() {
         assert_eq!(
             super::tilt(
-                &super::parse_into_platform(&EXAMPLE_INPUT),
+                &super::parse_into_platform(EXAMPLE_INPUT),
                 Direction::North
             ),
-            super::parse_into_platform(&TILTED_EXAMPLE_INPUT)
+            super::parse_into_platform(TILTED_EXAMPLE_INPUT)
         );
     }
 
     #[test]
     fn tilt_1_cycle() {
+        let mut dish = Dish::new(&super::parse_into_platform(EXAMPLE_INPUT));
+        dish.spin_cycle();
+
         assert_eq!(
-            super::tilt_one_cycle(&super::parse_into_platform(&EXAMPLE_INPUT)),
-            super::parse_into_platform(&EXAMPLE_INPUT_AFTER_1_CYCLE)
+            dish.to_platform(),
+            super::parse_into_platform(EXAMPLE_INPUT_AFTER_1_CYCLE)
         );
     }
 
+    #[test]
+    fn get_load_after_cycles() {
+        let platform = super::parse_into_platform(EXAMPLE_INPUT);
+
+        assert_eq!(super::get_load_after_cycles(&platform, 1), 87);
+        assert_eq!(super::get_load_after_cycles(&platform, 1000000000), 64);
+    }
+
+    #[test]
+    fn dish_state() {
+        let mut dish = Dish::new(&super::parse_into_platform(EXAMPLE_INPUT));
+        let before = dish.state();
+
+        dish.spin_cycle();
+        assert_ne!(dish.state(), before);
+        assert_eq!(dish.total_load(), 87);
+    }
+
     #[test]
     fn get_total_load() {
         assert_eq!(super::get_total_load(&get_parsed_small_input()), 5);
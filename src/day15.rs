@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::fmt;
 
-type Boxes = HashMap<usize, Vec<(String, Option<usize>)>>;
+use crate::solver::{Options, Solution};
+
+type Boxes = [Vec<(String, usize)>; 256];
 
 fn get_hash(input: &str) -> usize {
     input
@@ -8,82 +10,155 @@ fn get_hash(input: &str) -> usize {
         .fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Op {
+    Insert(String, usize),
+    Remove(String),
+}
+
 #[derive(Debug, Eq, PartialEq)]
-struct Operation {
-    operator: String,
-    label: String,
-    hash: usize,
-    val: Option<usize>,
+enum ParseError {
+    MissingOperator(String),
+    EmptyLabel(String),
+    InvalidFocalLength(String),
 }
 
-fn parse_operations(input: &str) -> Vec<Operation> {
-    input
-        .split(",")
-        .map(|op| {
-            let (label, value) = op.split_once(|c| c == '=' || c == '-').unwrap();
-            Operation {
-                operator: if value.is_empty() { '-' } else { '=' }.to_string(),
-                label: label.to_string(),
-                hash: get_hash(label),
-                val: if value.is_empty() {
-                    None
-                } else {
-                    Some(value.parse::<usize>().unwrap())
-                },
+impl Op {
+    fn label(&self) -> &str {
+        match self {
+            Op::Insert(label, _) | Op::Remove(label) => label,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Insert(label, focal) => write!(f, "{}={}", label, focal),
+            Op::Remove(label) => write!(f, "{}-", label),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingOperator(step) => write!(f, "step {:?} has no '=' or '-'", step),
+            ParseError::EmptyLabel(step) => write!(f, "step {:?} has an empty label", step),
+            ParseError::InvalidFocalLength(step) => {
+                write!(f, "step {:?} has an invalid focal length", step)
             }
-        })
+        }
+    }
+}
+
+fn parse_operation(step: &str) -> Result<Op, ParseError> {
+    let (label, value) = step
+        .split_once(['=', '-'])
+        .ok_or_else(|| ParseError::MissingOperator(step.to_string()))?;
+
+    if label.is_empty() {
+        return Err(ParseError::EmptyLabel(step.to_string()));
+    }
+
+    match step.as_bytes()[label.len()] {
+        b'=' => value
+            .parse()
+            .map(|focal| Op::Insert(label.to_string(), focal))
+            .map_err(|_| ParseError::InvalidFocalLength(step.to_string())),
+        _ if value.is_empty() => Ok(Op::Remove(label.to_string())),
+        _ => Err(ParseError::InvalidFocalLength(step.to_string())),
+    }
+}
+
+fn parse_operations(input: &str) -> Result<Vec<Op>, ParseError> {
+    input
+        .split(',')
+        .map(|step| parse_operation(step.trim()))
         .collect()
 }
 
+fn apply(boxes: &mut Boxes, op: &Op) {
+    let lenses = &mut boxes[get_hash(op.label())];
+
+    match op {
+        Op::Insert(label, focal) => {
+            if let Some((_, current)) = lenses.iter_mut().find(|(lens, _)| lens == label) {
+                *current = *focal;
+            } else {
+                lenses.push((label.clone(), *focal));
+            }
+        }
+        Op::Remove(label) => lenses.retain(|(lens, _)| lens != label),
+    }
+}
+
 fn calculate_focusing_power(boxes: &Boxes) -> usize {
     boxes
         .iter()
-        .map(|(hash, content)| {
-            content
+        .enumerate()
+        .map(|(box_idx, lenses)| {
+            lenses
                 .iter()
                 .enumerate()
-                .map(|(slot, (_, val))| (hash + 1) * (slot + 1) * val.unwrap())
+                .map(|(slot, (_, focal))| (box_idx + 1) * (slot + 1) * focal)
                 .sum::<usize>()
         })
         .sum::<usize>()
 }
 
-fn get_boxes(input: &str) -> Boxes {
-    let mut boxes: Boxes = HashMap::new();
-
-    parse_operations(input).iter().for_each(|op| {
-        let box_val = boxes.entry(op.hash).or_insert_with(Vec::new);
-
-        match op.operator.as_str() {
-            "=" => {
-                if let Some((_, val)) = box_val
-                    .iter_mut()
-                    .find(|(label, val)| label == op.label.as_str())
-                {
-                    *val = op.val;
-                } else {
-                    box_val.push((op.label.clone(), op.val));
-                }
-            }
-            "-" => {
-                box_val.retain(|(label, _)| label != op.label.as_str());
-            }
-            _ => panic!("Unknown operation"),
-        }
-    });
+fn get_boxes(ops: &[Op]) -> Boxes {
+    let mut boxes: Boxes = std::array::from_fn(|_| Vec::new());
+    ops.iter().for_each(|op| apply(&mut boxes, op));
+    boxes
+}
 
+fn format_boxes(boxes: &Boxes) -> String {
     boxes
+        .iter()
+        .enumerate()
+        .filter(|(_, lenses)| !lenses.is_empty())
+        .map(|(box_idx, lenses)| {
+            let lenses: Vec<_> = lenses
+                .iter()
+                .map(|(label, focal)| format!("[{} {}]", label, focal))
+                .collect();
+            format!("Box {}: {}\n", box_idx, lenses.join(" "))
+        })
+        .collect()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", input.split(",").map(|a| get_hash(a)).sum::<usize>());
-    print!("{}", calculate_focusing_power(&get_boxes(input)));
+fn trace(ops: &[Op]) -> String {
+    let mut boxes: Boxes = std::array::from_fn(|_| Vec::new());
+
+    ops.iter()
+        .map(|op| {
+            apply(&mut boxes, op);
+            format!("After \"{}\":\n{}", op, format_boxes(&boxes))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let ops = parse_operations(input).map_err(|error| error.to_string())?;
+    let mut solution = Solution::from(vec![
+        input.split(',').map(get_hash).sum::<usize>().to_string(),
+        calculate_focusing_power(&get_boxes(&ops)).to_string(),
+    ]);
+
+    if options.flag("--trace") {
+        solution.note(trace(&ops));
+    }
+    Ok(solution)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
     #[test]
     fn get_hash() {
         assert_eq!(super::get_hash("rn=1"), 30);
@@ -98,22 +173,32 @@ mod tests {
     fn parse_operations() {
         assert_eq!(
             super::parse_operations("rn=2"),
-            vec![super::Operation {
-                operator: "=".to_string(),
-                label: "rn".to_string(),
-                hash: 0,
-                val: Some(2),
-            }]
+            Ok(vec![Op::Insert("rn".to_string(), 2)])
         );
 
         assert_eq!(
             super::parse_operations("cm-"),
-            vec![super::Operation {
-                operator: "-".to_string(),
-                label: "cm".to_string(),
-                hash: 0,
-                val: None
-            }]
+            Ok(vec![Op::Remove("cm".to_string())])
+        );
+    }
+
+    #[test]
+    fn parse_malformed_operations() {
+        assert_eq!(
+            super::parse_operations("rn=1,cm"),
+            Err(ParseError::MissingOperator("cm".to_string()))
+        );
+        assert_eq!(
+            super::parse_operations("=3"),
+            Err(ParseError::EmptyLabel("=3".to_string()))
+        );
+        assert_eq!(
+            super::parse_operations("rn=x"),
+            Err(ParseError::InvalidFocalLength("rn=x".to_string()))
+        );
+        assert_eq!(
+            super::parse_operations("rn-4"),
+            Err(ParseError::InvalidFocalLength("rn-4".to_string()))
         );
     }
 
@@ -121,9 +206,32 @@ mod tests {
     fn calculate_focusing_power() {
         assert_eq!(
             super::calculate_focusing_power(&super::get_boxes(
-                "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"
+                &super::parse_operations(EXAMPLE_INPUT).unwrap()
             )),
             145
         );
     }
+
+    #[test]
+    fn trace() {
+        let ops = super::parse_operations(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            super::trace(&ops[..4]),
+            "After \"rn=1\":
+Box 0: [rn 1]
+
+After \"cm-\":
+Box 0: [rn 1]
+
+After \"qp=3\":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After \"cm=2\":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+"
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use std::{collections::{HashMap, HashSet}, vec};
 
+use crate::solver::{Options, Solution};
+
 #[derive(Debug, PartialEq, Clone, Copy, Eq)]
 enum Tile{
     Empty,
@@ -105,7 +107,7 @@ fn solve_1(facility: &Facility, entry_point: (i32, i32), direction: Direction) -
         visited_pos.extend(beam_pos.iter().cloned());
 
         beam_pos = get_next_all(facility, beam_pos);
-        if beam_pos.len() == 0 {
+        if beam_pos.is_empty() {
             break;
         }
     }
@@ -119,17 +121,16 @@ fn solve_2(facility: &Facility) -> usize
     let width = *facility.iter().map(|((x, _), _)| x).max().unwrap();
     let height = *facility.iter().map(|((_, y), _)| y).max().unwrap();
 
-    let right_max = (0..height).into_iter().map(|y| solve_1(facility, (-1, y), Direction::Right)).max();
-    let left_max = (0..height).into_iter().map(|y| solve_1(facility, (width, y), Direction::Left)).max();
-    let down_max = (0..width).into_iter().map(|x| solve_1(facility, (x, -1), Direction::Down)).max();
-    let up_max = (0..width).into_iter().map(|x| solve_1(facility, (x, height), Direction::Up)).max();
+    let right_max = (0..height).map(|y| solve_1(facility, (-1, y), Direction::Right)).max();
+    let left_max = (0..height).map(|y| solve_1(facility, (width, y), Direction::Left)).max();
+    let down_max = (0..width).map(|x| solve_1(facility, (x, -1), Direction::Down)).max();
+    let up_max = (0..width).map(|x| solve_1(facility, (x, height), Direction::Up)).max();
 
     right_max.max(left_max).max(down_max).max(up_max).unwrap()
 }
 
-pub fn solve(input: &str) {
-    println!("{}", solve_1(&parse(&input), (-1, 0), Direction::Right));
-    println!("{}", solve_2(&parse(&input)));
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(&parse(input), (-1, 0), Direction::Right).to_string(), solve_2(&parse(input)).to_string()].into())
 }
 
 #[cfg(test)]
@@ -149,12 +150,12 @@ mod tests {
 
 #[test]
 fn solve_1() {
-    assert_eq!(super::solve_1(&parse(&EXAMPLE_INPUT), (-1, 0), Direction::Right), 46);
+    assert_eq!(super::solve_1(&parse(EXAMPLE_INPUT), (-1, 0), Direction::Right), 46);
 }
 
 #[test]
 fn solve_2(){
-    assert_eq!(super::solve_2(&parse(&EXAMPLE_INPUT)), 51);
+    assert_eq!(super::solve_2(&parse(EXAMPLE_INPUT)), 51);
 }
 
 
@@ -1,4 +1,7 @@
 use std::collections::HashMap;
+use std::fmt;
+
+use crate::solver::{Options, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Part {
@@ -8,7 +11,7 @@ struct Part {
     s: i32,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 enum Operator {
     LessThan,
     GreaterThan,
@@ -29,89 +32,202 @@ struct Workflow {
 
 type Workflows = HashMap<String, Workflow>;
 
+const ELEMENTS: [&str; 4] = ["x", "m", "a", "s"];
+const ACCEPT: usize = 0;
+const REJECT: usize = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Node {
+    Accept,
+    Reject,
+    Branch {
+        element: usize,
+        operator: Operator,
+        reference: i32,
+        then: usize,
+        otherwise: usize,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DecisionTree {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+struct Compiler<'a> {
+    workflows: &'a Workflows,
+    nodes: Vec<Node>,
+    interned_nodes: HashMap<Node, usize>,
+    compiled_workflows: HashMap<&'a str, usize>,
+}
+
 impl Part {
-    fn get(&self, element: &str) -> i32 {
-        match element {
-            "x" => self.x,
-            "m" => self.m,
-            "a" => self.a,
-            "s" => self.s,
-            _ => panic!("Unknown element {}", element),
+    fn elements(&self) -> [i32; 4] {
+        [self.x, self.m, self.a, self.s]
+    }
+}
+
+impl<'a> Compiler<'a> {
+    fn compile_target(&mut self, target: &'a str) -> usize {
+        match target {
+            "A" => ACCEPT,
+            "R" => REJECT,
+            name => {
+                if let Some(&idx) = self.compiled_workflows.get(name) {
+                    return idx;
+                }
+                let idx = self.compile_rules(&self.workflows[name].rules);
+                self.compiled_workflows.insert(name, idx);
+                idx
+            }
+        }
+    }
+
+    fn compile_rules(&mut self, rules: &'a [Rule]) -> usize {
+        // validate() reports workflows that parts can fall off the end of
+        let Some((rule, rest)) = rules.split_first() else {
+            return REJECT;
+        };
+        let then = self.compile_target(&rule.target);
+
+        let (Some(element), Some(operator), Some(reference)) =
+            (rule.element.as_ref(), rule.operator, rule.reference)
+        else {
+            return then;
+        };
+
+        let otherwise = self.compile_rules(rest);
+        if then == otherwise {
+            return then;
         }
+
+        let node = Node::Branch {
+            element: ELEMENTS.iter().position(|name| name == element).unwrap(),
+            operator,
+            reference,
+            then,
+            otherwise,
+        };
+        *self.interned_nodes.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        })
     }
 }
 
-fn apply_operator(rule: &Rule, part: &Part) -> bool {
-    let Some(element) = rule.element.as_ref() else {
-        return true;
+fn compile(workflows: &Workflows) -> DecisionTree {
+    let mut compiler = Compiler {
+        workflows,
+        nodes: vec![Node::Accept, Node::Reject],
+        interned_nodes: HashMap::new(),
+        compiled_workflows: HashMap::new(),
     };
+    let root = compiler.compile_target("in");
+
+    DecisionTree {
+        nodes: compiler.nodes,
+        root,
+    }
+}
+
+fn evaluate(tree: &DecisionTree, part: &Part) -> bool {
+    let elements = part.elements();
+    let mut idx = tree.root;
 
-    match rule.operator {
-        Some(Operator::LessThan) => part.get(&element) < rule.reference.unwrap(),
-        Some(Operator::GreaterThan) => part.get(&element) > rule.reference.unwrap(),
-        None => true,
+    loop {
+        match tree.nodes[idx] {
+            Node::Accept => return true,
+            Node::Reject => return false,
+            Node::Branch {
+                element,
+                operator,
+                reference,
+                then,
+                otherwise,
+            } => {
+                let matches = match operator {
+                    Operator::LessThan => elements[element] < reference,
+                    Operator::GreaterThan => elements[element] > reference,
+                };
+                idx = if matches { then } else { otherwise };
+            }
+        }
     }
 }
 
-fn do_workflow(workflows: &Workflows, part: &Part, current_workflow_name: &str) -> bool {
-    let current_workflow = workflows.get(current_workflow_name).unwrap();
+fn write_node(tree: &DecisionTree, idx: usize, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
 
-    for rule in &current_workflow.rules {
-        if apply_operator(&rule, &part) {
-            let target_name = rule.target.as_str();
-            return match target_name {
-                "A" => true,
-                "R" => false,
-                _ => do_workflow(workflows, part, target_name),
+    match tree.nodes[idx] {
+        Node::Accept => *output += &format!("{indent}A\n"),
+        Node::Reject => *output += &format!("{indent}R\n"),
+        Node::Branch {
+            element,
+            operator,
+            reference,
+            then,
+            otherwise,
+        } => {
+            let operator = match operator {
+                Operator::LessThan => '<',
+                Operator::GreaterThan => '>',
             };
-        } else {
-            continue;
+            *output += &format!("{indent}if {} {operator} {reference}\n", ELEMENTS[element]);
+            write_node(tree, then, depth + 1, output);
+            *output += &format!("{indent}else\n");
+            write_node(tree, otherwise, depth + 1, output);
         }
     }
+}
 
-    unreachable!()
+fn print_tree(tree: &DecisionTree) -> String {
+    let mut output = String::new();
+    write_node(tree, tree.root, 0, &mut output);
+    output
 }
 
-fn parse(input: &str) -> (HashMap<String, Workflow>, Vec<Part>) {
+fn parse(input: &str) -> Result<(HashMap<String, Workflow>, Vec<Part>), String> {
     let mut workflows = HashMap::new();
     let mut parts = vec![];
 
     for line in input.lines() {
         if line.starts_with('{') {
-            let part = line
+            let ratings = line
                 .trim_start_matches('{')
                 .trim_end_matches('}')
                 .split(',')
                 .map(|pair| {
-                    let mut iter = pair.split('=');
-                    let key = iter.next().unwrap().trim();
-                    let value = iter.next().unwrap().trim().parse().unwrap();
-                    (key, value)
+                    let (key, value) = pair.split_once('=')?;
+                    Some((key.trim(), value.trim().parse().ok()?))
                 })
-                .fold(
-                    Part {
-                        x: 0,
-                        m: 0,
-                        a: 0,
-                        s: 0,
-                    },
-                    |mut part, (key, value)| {
-                        match key {
-                            "x" => part.x = value,
-                            "m" => part.m = value,
-                            "a" => part.a = value,
-                            "s" => part.s = value,
-                            _ => (),
-                        }
-                        part
-                    },
-                );
+                .collect::<Option<Vec<_>>>()
+                .ok_or(format!("invalid part {:?}", line))?;
+            let part = ratings.into_iter().fold(
+                Part {
+                    x: 0,
+                    m: 0,
+                    a: 0,
+                    s: 0,
+                },
+                |mut part, (key, value)| {
+                    match key {
+                        "x" => part.x = value,
+                        "m" => part.m = value,
+                        "a" => part.a = value,
+                        "s" => part.s = value,
+                        _ => (),
+                    }
+                    part
+                },
+            );
 
             parts.push(part);
-        } else if let Some(_) = line.chars().next() {
-            let mut iter = line.split('{');
-            let name = iter.next().unwrap().trim();
-            let rules = iter.next().unwrap().trim_end_matches('}');
+        } else if line.chars().next().is_some() {
+            let (name, rules) = line
+                .split_once('{')
+                .ok_or(format!("invalid workflow {:?}", line))?;
+            let (name, rules) = (name.trim(), rules.trim_end_matches('}'));
 
             workflows.insert(
                 name.to_string(),
@@ -121,7 +237,7 @@ fn parse(input: &str) -> (HashMap<String, Workflow>, Vec<Part>) {
                         .map(|rule| match rule.split_once(':') {
                             Some((condition, target)) => {
                                 let mut iter = condition.chars();
-                                let element = iter.next().map(|c| c.to_string());
+                                let element = Some(iter.next().map_or(String::new(), String::from));
                                 let operator = match iter.next() {
                                     Some('<') => Some(Operator::LessThan),
                                     Some('>') => Some(Operator::GreaterThan),
@@ -149,19 +265,213 @@ fn parse(input: &str) -> (HashMap<String, Workflow>, Vec<Part>) {
         }
     }
 
-    (workflows, parts)
+    Ok((workflows, parts))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ValidationError {
+    MissingStart,
+    UnknownTarget { workflow: String, target: String },
+    UnknownElement { workflow: String, element: String },
+    MalformedRule { workflow: String, rule_idx: usize },
+    Cycle(Vec<String>),
+    DeadRule { workflow: String, rule_idx: usize },
+    NoMatchingRule { workflow: String },
+}
+
+impl ValidationError {
+    fn is_fatal(&self) -> bool {
+        !matches!(self, ValidationError::DeadRule { .. })
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::MissingStart => write!(f, "no workflow named 'in'"),
+            ValidationError::UnknownTarget { workflow, target } => {
+                write!(f, "{}: target '{}' is not a workflow", workflow, target)
+            }
+            ValidationError::UnknownElement { workflow, element } => {
+                write!(f, "{}: unknown element '{}'", workflow, element)
+            }
+            ValidationError::MalformedRule { workflow, rule_idx } => {
+                write!(f, "{}: rule {} is malformed", workflow, rule_idx + 1)
+            }
+            ValidationError::Cycle(names) => write!(f, "cycle: {}", names.join(" -> ")),
+            ValidationError::DeadRule { workflow, rule_idx } => {
+                write!(f, "{}: rule {} can never fire", workflow, rule_idx + 1)
+            }
+            ValidationError::NoMatchingRule { workflow } => {
+                write!(f, "{}: some parts match no rule", workflow)
+            }
+        }
+    }
+}
+
+type Ranges = HashMap<String, (i32, i32)>;
+
+fn split_ranges(rule: &Rule, ranges: &Ranges) -> (Option<Ranges>, Option<Ranges>) {
+    let (Some(element), Some(operator), Some(reference)) =
+        (rule.element.as_ref(), rule.operator, rule.reference)
+    else {
+        return (Some(ranges.clone()), None);
+    };
+
+    let (min, max) = ranges[element];
+    let (matching, remaining) = match operator {
+        Operator::LessThan => ((min, max.min(reference - 1)), (min.max(reference), max)),
+        Operator::GreaterThan => ((min.max(reference + 1), max), (min, max.min(reference))),
+    };
+
+    let with = |range: (i32, i32)| {
+        (range.0 <= range.1).then(|| {
+            let mut ranges = ranges.clone();
+            ranges.insert(element.clone(), range);
+            ranges
+        })
+    };
+
+    (with(matching), with(remaining))
 }
 
-pub fn solve(input: &str) {
-    let (workflows, parts) = parse(input);
-    println!(
-        "{}",
-        parts
+fn find_cycle<'a>(
+    workflows: &'a Workflows,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    done: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    if let Some(start) = path.iter().position(|&visited| visited == name) {
+        let mut cycle: Vec<_> = path[start..].iter().map(|name| name.to_string()).collect();
+        cycle.push(name.to_string());
+        return Some(cycle);
+    }
+    if done.contains(&name) {
+        return None;
+    }
+
+    path.push(name);
+    let cycle = workflows.get(name).and_then(|workflow| {
+        workflow
+            .rules
             .iter()
-            .filter(|part| do_workflow(&workflows, part, "in"))
-            .map(|Part { x, m, a, s }| x + m + a + s)
-            .sum::<i32>()
-    );
+            .find_map(|rule| find_cycle(workflows, &rule.target, path, done))
+    });
+    path.pop();
+    done.push(name);
+
+    cycle
+}
+
+fn validate(workflows: &Workflows) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    if !workflows.contains_key("in") {
+        errors.push(ValidationError::MissingStart);
+    }
+
+    let mut names: Vec<_> = workflows.keys().collect();
+    names.sort();
+
+    for name in &names {
+        let rules = &workflows[*name].rules;
+        let mut malformed = false;
+
+        for (rule_idx, rule) in rules.iter().enumerate() {
+            if !matches!(rule.target.as_str(), "A" | "R") && !workflows.contains_key(&rule.target) {
+                errors.push(ValidationError::UnknownTarget {
+                    workflow: name.to_string(),
+                    target: rule.target.clone(),
+                });
+            }
+
+            match &rule.element {
+                Some(_) if rule.operator.is_none() || rule.reference.is_none() => {
+                    errors.push(ValidationError::MalformedRule {
+                        workflow: name.to_string(),
+                        rule_idx,
+                    });
+                    malformed = true;
+                }
+                Some(element) if !ELEMENTS.contains(&element.as_str()) => {
+                    errors.push(ValidationError::UnknownElement {
+                        workflow: name.to_string(),
+                        element: element.clone(),
+                    });
+                    malformed = true;
+                }
+                _ => (),
+            }
+        }
+        if malformed {
+            continue;
+        }
+
+        let mut remaining = Some(
+            ELEMENTS
+                .into_iter()
+                .map(|element| (element.to_string(), (1, 4000)))
+                .collect(),
+        );
+        for (rule_idx, rule) in rules.iter().enumerate() {
+            let (matching, rest) = match &remaining {
+                Some(ranges) => split_ranges(rule, ranges),
+                None => (None, None),
+            };
+            if matching.is_none() {
+                errors.push(ValidationError::DeadRule {
+                    workflow: name.to_string(),
+                    rule_idx,
+                });
+            }
+            remaining = rest;
+        }
+
+        if remaining.is_some() {
+            errors.push(ValidationError::NoMatchingRule {
+                workflow: name.to_string(),
+            });
+        }
+    }
+
+    let mut done = vec![];
+    for name in &names {
+        if let Some(cycle) = find_cycle(workflows, name, &mut vec![], &mut done) {
+            errors.push(ValidationError::Cycle(cycle));
+        }
+    }
+
+    errors
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let (workflows, parts) = parse(input)?;
+
+    let (fatal, warnings): (Vec<_>, Vec<_>) = validate(&workflows)
+        .into_iter()
+        .partition(ValidationError::is_fatal);
+    if !fatal.is_empty() {
+        let errors: Vec<_> = fatal.iter().map(ValidationError::to_string).collect();
+        return Err(errors.join("; "));
+    }
+
+    let mut solution = Solution::default();
+    for warning in &warnings {
+        solution.note(warning.to_string());
+    }
+
+    let tree = compile(&workflows);
+    if options.flag("--tree") {
+        solution.note(print_tree(&tree));
+    }
+
+    solution.answers = vec![parts
+        .iter()
+        .filter(|part| evaluate(&tree, part))
+        .map(|Part { x, m, a, s }| x + m + a + s)
+        .sum::<i32>()
+        .to_string()];
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -189,8 +499,8 @@ hdj{m>838:A,pv}
     #[test]
     fn parse() {
         assert_eq!(
-            super::parse(&EXAMPLE_INPUT.lines().next().unwrap()),
-            (
+            super::parse(EXAMPLE_INPUT.lines().next().unwrap()),
+            Ok((
                 HashMap::from_iter(vec![(
                     "px".to_string(),
                     Workflow {
@@ -217,15 +527,15 @@ hdj{m>838:A,pv}
                     }
                 )]),
                 Vec::new()
-            )
+            ))
         )
     }
 
     #[test]
     fn parse_2() {
         assert_eq!(
-            super::parse(&EXAMPLE_INPUT.lines().last().unwrap()),
-            (
+            super::parse(EXAMPLE_INPUT.lines().last().unwrap()),
+            Ok((
                 HashMap::new(),
                 vec![Part {
                     x: 2127,
@@ -233,16 +543,128 @@ hdj{m>838:A,pv}
                     a: 2188,
                     s: 1013,
                 }]
-            )
+            ))
+        );
+        assert!(super::parse("{x=1,m}").is_err());
+        assert!(super::parse("in").is_err());
+    }
+
+    #[test]
+    fn evaluate() {
+        let (workflows, parts) = super::parse(EXAMPLE_INPUT).unwrap();
+        let tree = super::compile(&workflows);
+
+        assert_eq!(
+            parts
+                .iter()
+                .map(|part| super::evaluate(&tree, part))
+                .collect::<Vec<_>>(),
+            vec![true, false, true, false, true]
         );
     }
 
     #[test]
-    fn do_workflow() {
-        let parsed_input = super::parse(&EXAMPLE_INPUT);
+    fn compile() {
+        let tree = super::compile(&super::parse("in{a>3333:R,R}").unwrap().0);
+        assert_eq!(tree.nodes[tree.root], Node::Reject);
+
+        let tree = super::compile(&super::parse("in{x<10:a,a}\na{m>5:R,A}").unwrap().0);
+        assert_eq!(tree.nodes.len(), 3);
+
+        let tree = super::compile(&super::parse("in{x>0:A}").unwrap().0);
+        assert_eq!(tree.nodes.len(), 3);
+    }
+
+    #[test]
+    fn print_tree() {
+        let tree = super::compile(&super::parse("in{x<10:A,lnx}\nlnx{m>5:R,A}").unwrap().0);
+        assert_eq!(
+            super::print_tree(&tree),
+            "if x < 10
+  A
+else
+  if m > 5
+    R
+  else
+    A
+"
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            super::validate(&super::parse(EXAMPLE_INPUT).unwrap().0),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn validate_broken_workflows() {
+        let (workflows, _) = super::parse(
+            "in{x<10:a,x<5:R,b}
+a{m>100:in,m<200:A,R}
+b{s>10:c}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            super::validate(&workflows),
+            vec![
+                ValidationError::DeadRule {
+                    workflow: "a".to_string(),
+                    rule_idx: 2
+                },
+                ValidationError::UnknownTarget {
+                    workflow: "b".to_string(),
+                    target: "c".to_string()
+                },
+                ValidationError::NoMatchingRule {
+                    workflow: "b".to_string()
+                },
+                ValidationError::DeadRule {
+                    workflow: "in".to_string(),
+                    rule_idx: 1
+                },
+                ValidationError::Cycle(vec!["a".to_string(), "in".to_string(), "a".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_malformed_rules() {
+        let (workflows, _) = super::parse("in{q<5:A,x?5:R,A}").unwrap();
+
+        assert_eq!(
+            super::validate(&workflows),
+            vec![
+                ValidationError::UnknownElement {
+                    workflow: "in".to_string(),
+                    element: "q".to_string()
+                },
+                ValidationError::MalformedRule {
+                    workflow: "in".to_string(),
+                    rule_idx: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn solve() {
+        let options = Options::default();
+
+        assert_eq!(
+            super::solve(EXAMPLE_INPUT, &options),
+            Ok(vec!["19114".to_string()].into())
+        );
+        assert_eq!(
+            super::solve("in{x<10:a,R}\n\n{x=1,m=2,a=3,s=4}", &options),
+            Err("in: target 'a' is not a workflow".to_string())
+        );
         assert_eq!(
-            super::do_workflow(&parsed_input.0, &parsed_input.1.first().unwrap(), "in"),
-            true
+            super::solve("in{q<5:A,R}\n\n{x=1,m=2,a=3,s=4}", &options),
+            Err("in: unknown element 'q'".to_string())
         );
     }
 }
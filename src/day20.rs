@@ -0,0 +1,393 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::solver::{Options, Solution};
+
+const MAX_PRESSES: usize = 100_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Module {
+    kind: ModuleKind,
+    destinations: Vec<String>,
+}
+
+type Modules = HashMap<String, Module>;
+
+#[derive(Debug, Default)]
+struct State {
+    flip_flops: HashMap<String, bool>,
+    conjunctions: HashMap<String, HashMap<String, Pulse>>,
+}
+
+fn parse(input: &str) -> Result<Modules, String> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, destinations) = line
+                .split_once(" -> ")
+                .ok_or(format!("invalid module {:?}", line))?;
+            let (kind, name) = match name.chars().next() {
+                Some('%') => (ModuleKind::FlipFlop, &name[1..]),
+                Some('&') => (ModuleKind::Conjunction, &name[1..]),
+                _ => (ModuleKind::Broadcaster, name),
+            };
+            if name.is_empty() {
+                return Err(format!("invalid module {:?}", line));
+            }
+
+            Ok((
+                name.to_string(),
+                Module {
+                    kind,
+                    destinations: destinations.split(", ").map(String::from).collect(),
+                },
+            ))
+        })
+        .collect()
+}
+
+fn get_inputs<'a>(modules: &'a Modules, name: &str) -> Vec<&'a str> {
+    let mut inputs: Vec<_> = modules
+        .iter()
+        .filter(|(_, module)| module.destinations.iter().any(|dest| dest == name))
+        .map(|(input, _)| input.as_str())
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+fn init_state(modules: &Modules) -> State {
+    let mut state = State::default();
+
+    for (name, module) in modules {
+        match module.kind {
+            ModuleKind::FlipFlop => {
+                state.flip_flops.insert(name.clone(), false);
+            }
+            ModuleKind::Conjunction => {
+                state.conjunctions.insert(
+                    name.clone(),
+                    get_inputs(modules, name)
+                        .into_iter()
+                        .map(|input| (input.to_string(), Pulse::Low))
+                        .collect(),
+                );
+            }
+            ModuleKind::Broadcaster => (),
+        }
+    }
+
+    state
+}
+
+fn press_button(modules: &Modules, state: &mut State, mut on_pulse: impl FnMut(&str, &str, Pulse)) {
+    let mut queue = VecDeque::from([("button".to_string(), "broadcaster".to_string(), Pulse::Low)]);
+
+    while let Some((from, to, pulse)) = queue.pop_front() {
+        on_pulse(&from, &to, pulse);
+
+        let Some(module) = modules.get(&to) else {
+            continue;
+        };
+
+        let outgoing = match module.kind {
+            ModuleKind::Broadcaster => pulse,
+            ModuleKind::FlipFlop => {
+                if pulse == Pulse::High {
+                    continue;
+                }
+                let is_on = state.flip_flops.get_mut(&to).unwrap();
+                *is_on = !*is_on;
+                if *is_on {
+                    Pulse::High
+                } else {
+                    Pulse::Low
+                }
+            }
+            ModuleKind::Conjunction => {
+                let memory = state.conjunctions.get_mut(&to).unwrap();
+                memory.insert(from, pulse);
+                if memory.values().all(|&pulse| pulse == Pulse::High) {
+                    Pulse::Low
+                } else {
+                    Pulse::High
+                }
+            }
+        };
+
+        for dest in &module.destinations {
+            queue.push_back((to.clone(), dest.clone(), outgoing));
+        }
+    }
+}
+
+fn count_pulses(modules: &Modules, presses: usize) -> (usize, usize) {
+    let mut state = init_state(modules);
+    let (mut low, mut high) = (0, 0);
+
+    for _ in 0..presses {
+        press_button(modules, &mut state, |_, _, pulse| match pulse {
+            Pulse::Low => low += 1,
+            Pulse::High => high += 1,
+        });
+    }
+
+    (low, high)
+}
+
+fn solve_1(modules: &Modules) -> usize {
+    let (low, high) = count_pulses(modules, 1000);
+    low * high
+}
+
+fn get_cycle_lengths(
+    modules: &Modules,
+    target: &str,
+    max_presses: usize,
+) -> Result<BTreeMap<String, HashMap<String, usize>>, String> {
+    let feeders = get_inputs(modules, target);
+    if feeders.is_empty() {
+        return Err(format!("no conjunction feeds {}", target));
+    }
+    if let Some(feeder) = feeders
+        .iter()
+        .find(|feeder| modules[**feeder].kind != ModuleKind::Conjunction)
+    {
+        return Err(format!(
+            "{} feeds {} but is not a conjunction",
+            feeder, target
+        ));
+    }
+
+    let mut cycle_lengths: BTreeMap<_, _> = feeders
+        .iter()
+        .map(|feeder| (feeder.to_string(), HashMap::new()))
+        .collect();
+    let mut state = init_state(modules);
+    let incomplete = |cycle_lengths: &BTreeMap<String, HashMap<String, usize>>| {
+        feeders
+            .iter()
+            .copied()
+            .find(|feeder| cycle_lengths[*feeder].len() < get_inputs(modules, feeder).len())
+    };
+
+    for presses in 1..=max_presses {
+        press_button(modules, &mut state, |from, to, pulse| {
+            if pulse == Pulse::High {
+                if let Some(lengths) = cycle_lengths.get_mut(to) {
+                    lengths.entry(from.to_string()).or_insert(presses);
+                }
+            }
+        });
+
+        if incomplete(&cycle_lengths).is_none() {
+            return Ok(cycle_lengths);
+        }
+    }
+
+    Err(format!(
+        "not every input of {} sent a high pulse within {} presses",
+        incomplete(&cycle_lengths).unwrap_or_default(),
+        max_presses
+    ))
+}
+
+fn solve_2(modules: &Modules) -> Result<usize, String> {
+    // rx gets a low pulse as soon as any of its feeders has seen all inputs high
+    get_cycle_lengths(modules, "rx", MAX_PRESSES)?
+        .values()
+        .map(|lengths| {
+            lengths
+                .values()
+                .fold(1, |acc, &length| num_integer::lcm(acc, length))
+        })
+        .min()
+        .ok_or("no conjunction feeds rx".to_string())
+}
+
+fn to_dot(modules: &Modules) -> String {
+    let mut names: Vec<_> = modules.keys().collect();
+    names.sort();
+
+    let mut dot = String::from("digraph modules {\n");
+
+    for name in &names {
+        let (shape, prefix) = match modules[*name].kind {
+            ModuleKind::Broadcaster => ("doublecircle", ""),
+            ModuleKind::FlipFlop => ("box", "%"),
+            ModuleKind::Conjunction => ("diamond", "&"),
+        };
+        dot += &format!("    {name} [shape={shape}, label=\"{prefix}{name}\"];\n");
+    }
+
+    for name in &names {
+        for dest in &modules[*name].destinations {
+            dot += &format!("    {name} -> {dest};\n");
+        }
+    }
+
+    dot + "}\n"
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let modules = parse(input)?;
+
+    let mut solution = Solution::from(vec![solve_1(&modules).to_string()]);
+    if options.flag("--dot") {
+        solution.note(to_dot(&modules));
+    }
+
+    if !get_inputs(&modules, "rx").is_empty() {
+        solution.answers.push(solve_2(&modules)?.to_string());
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const EXAMPLE_INPUT_2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    const RX_INPUT: &str = "broadcaster -> a, c
+%a -> b
+%b -> x
+&x -> hub
+%c -> y
+&y -> hub
+&hub -> rx";
+
+    #[test]
+    fn parse() {
+        let modules = super::parse(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            modules["broadcaster"],
+            Module {
+                kind: ModuleKind::Broadcaster,
+                destinations: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            }
+        );
+        assert_eq!(
+            modules["inv"],
+            Module {
+                kind: ModuleKind::Conjunction,
+                destinations: vec!["a".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn count_pulses() {
+        assert_eq!(
+            super::count_pulses(&super::parse(EXAMPLE_INPUT).unwrap(), 1),
+            (8, 4)
+        );
+        assert_eq!(
+            super::count_pulses(&super::parse(EXAMPLE_INPUT).unwrap(), 1000),
+            (8000, 4000)
+        );
+    }
+
+    #[test]
+    fn solve_1() {
+        assert_eq!(
+            super::solve_1(&super::parse(EXAMPLE_INPUT).unwrap()),
+            32000000
+        );
+        assert_eq!(
+            super::solve_1(&super::parse(EXAMPLE_INPUT_2).unwrap()),
+            11687500
+        );
+    }
+
+    #[test]
+    fn get_cycle_lengths() {
+        assert_eq!(
+            super::get_cycle_lengths(&super::parse(RX_INPUT).unwrap(), "rx", 10),
+            Ok(BTreeMap::from([(
+                "hub".to_string(),
+                HashMap::from([("x".to_string(), 4), ("y".to_string(), 2)])
+            )]))
+        );
+        assert_eq!(
+            super::get_cycle_lengths(&super::parse(RX_INPUT).unwrap(), "rx", 3),
+            Err("not every input of hub sent a high pulse within 3 presses".to_string())
+        );
+    }
+
+    #[test]
+    fn solve_2() {
+        assert_eq!(super::solve_2(&super::parse(RX_INPUT).unwrap()), Ok(4));
+        assert_eq!(
+            super::solve_2(&super::parse("broadcaster -> a\n%a -> rx").unwrap()),
+            Err("a feeds rx but is not a conjunction".to_string())
+        );
+        assert_eq!(
+            super::solve_2(&super::parse("broadcaster -> a\n%a -> b").unwrap()),
+            Err("no conjunction feeds rx".to_string())
+        );
+    }
+
+    #[test]
+    fn solve_2_with_several_feeders() {
+        let modules =
+            super::parse(&RX_INPUT.replace("&y -> hub", "&y -> hub, solo\n&solo -> rx")).unwrap();
+
+        assert_eq!(
+            super::get_cycle_lengths(&modules, "rx", 10)
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec!["hub", "solo"]
+        );
+        assert_eq!(super::solve_2(&modules), Ok(2));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            super::parse("broadcaster -> a\n%a"),
+            Err("invalid module \"%a\"".to_string())
+        );
+        assert!(super::parse("% -> a").is_err());
+    }
+
+    #[test]
+    fn to_dot() {
+        assert_eq!(
+            super::to_dot(&super::parse("broadcaster -> a\n%a -> inv\n&inv -> a").unwrap()),
+            "digraph modules {
+    a [shape=box, label=\"%a\"];
+    broadcaster [shape=doublecircle, label=\"broadcaster\"];
+    inv [shape=diamond, label=\"&inv\"];
+    a -> inv;
+    broadcaster -> a;
+    inv -> a;
+}
+"
+        );
+    }
+}
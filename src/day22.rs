@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::solver::{Options, Solution};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Brick {
+    from: (usize, usize, usize),
+    to: (usize, usize, usize),
+}
+
+#[derive(Debug, PartialEq, Eq, Default)]
+struct SupportGraph {
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Brick {
+    fn columns(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.from.0..=self.to.0).flat_map(move |x| (self.from.1..=self.to.1).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> usize {
+        self.to.2 - self.from.2
+    }
+}
+
+fn parse_coordinates(input: &str) -> (usize, usize, usize) {
+    let mut iter = input
+        .split(',')
+        .map(|number| number.trim().parse().unwrap());
+    (
+        iter.next().unwrap(),
+        iter.next().unwrap(),
+        iter.next().unwrap(),
+    )
+}
+
+fn parse(input: &str) -> Vec<Brick> {
+    input
+        .lines()
+        .map(|line| {
+            let (from, to) = line.split_once('~').unwrap();
+            let (from, to) = (parse_coordinates(from), parse_coordinates(to));
+
+            Brick {
+                from: (from.0.min(to.0), from.1.min(to.1), from.2.min(to.2)),
+                to: (from.0.max(to.0), from.1.max(to.1), from.2.max(to.2)),
+            }
+        })
+        .collect()
+}
+
+fn settle(bricks: &[Brick]) -> (Vec<Brick>, SupportGraph) {
+    let mut falling = bricks.to_vec();
+    falling.sort_by_key(|brick| brick.from.2);
+
+    let mut height_map: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut graph = SupportGraph {
+        supports: vec![vec![]; falling.len()],
+        supported_by: vec![vec![]; falling.len()],
+    };
+
+    for (idx, brick) in falling.iter_mut().enumerate() {
+        let top = brick
+            .columns()
+            .filter_map(|column| height_map.get(&column))
+            .map(|&(z, _)| z)
+            .max()
+            .unwrap_or(0);
+
+        let mut supporters: Vec<_> = brick
+            .columns()
+            .filter_map(|column| height_map.get(&column))
+            .filter(|&&(z, _)| z == top)
+            .map(|&(_, supporter)| supporter)
+            .collect();
+        supporters.sort();
+        supporters.dedup();
+
+        for &supporter in &supporters {
+            graph.supports[supporter].push(idx);
+        }
+        graph.supported_by[idx] = supporters;
+
+        let height = brick.height();
+        brick.from.2 = top + 1;
+        brick.to.2 = top + 1 + height;
+
+        for column in brick.columns() {
+            height_map.insert(column, (brick.to.2, idx));
+        }
+    }
+
+    (falling, graph)
+}
+
+fn get_safe_to_disintegrate(graph: &SupportGraph) -> Vec<usize> {
+    (0..graph.supports.len())
+        .filter(|&idx| {
+            graph.supports[idx]
+                .iter()
+                .all(|&above| graph.supported_by[above].len() > 1)
+        })
+        .collect()
+}
+
+fn get_common_dominator(
+    dominators: &[Option<usize>],
+    depths: &[usize],
+    mut a: Option<usize>,
+    mut b: Option<usize>,
+) -> Option<usize> {
+    let depth = |brick: Option<usize>| brick.map_or(0, |idx| depths[idx]);
+
+    while a != b {
+        if depth(a) >= depth(b) {
+            a = dominators[a.unwrap()];
+        } else {
+            b = dominators[b.unwrap()];
+        }
+    }
+
+    a
+}
+
+fn get_chain_reaction_counts(graph: &SupportGraph) -> Vec<usize> {
+    let brick_count = graph.supported_by.len();
+    let mut dominators: Vec<Option<usize>> = vec![None; brick_count];
+    let mut depths = vec![0; brick_count];
+
+    // Bricks are indexed in settling order, so every supporter is visited before the bricks
+    // resting on it. A brick falls together with exactly the bricks it dominates.
+    for idx in 0..brick_count {
+        let dominator = graph.supported_by[idx]
+            .iter()
+            .map(|&supporter| Some(supporter))
+            .reduce(|a, b| get_common_dominator(&dominators, &depths, a, b))
+            .flatten();
+
+        dominators[idx] = dominator;
+        depths[idx] = dominator.map_or(0, |dominator| depths[dominator]) + 1;
+    }
+
+    let mut subtree_sizes = vec![1; brick_count];
+    for idx in (0..brick_count).rev() {
+        if let Some(dominator) = dominators[idx] {
+            subtree_sizes[dominator] += subtree_sizes[idx];
+        }
+    }
+
+    subtree_sizes.into_iter().map(|size| size - 1).collect()
+}
+
+fn solve_1(input: &str) -> usize {
+    let (_, graph) = settle(&parse(input));
+    get_safe_to_disintegrate(&graph).len()
+}
+
+fn solve_2(input: &str) -> usize {
+    let (_, graph) = settle(&parse(input));
+    get_chain_reaction_counts(&graph).iter().sum()
+}
+
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(input).to_string(), solve_2(input).to_string()].into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse("2,2,2~2,2,2\n1,0,1~1,2,1").last(),
+            Some(&Brick {
+                from: (1, 0, 1),
+                to: (1, 2, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn settle() {
+        let (bricks, graph) = super::settle(&super::parse(EXAMPLE_INPUT));
+
+        assert_eq!(
+            bricks.last(),
+            Some(&Brick {
+                from: (1, 1, 5),
+                to: (1, 1, 6),
+            })
+        );
+        assert_eq!(graph.supports[0], vec![1, 2]);
+        assert_eq!(graph.supported_by[3], vec![1, 2]);
+    }
+
+    #[test]
+    fn solve_1() {
+        assert_eq!(super::solve_1(EXAMPLE_INPUT), 5);
+    }
+
+    #[test]
+    fn get_chain_reaction_counts() {
+        let (_, graph) = super::settle(&super::parse(EXAMPLE_INPUT));
+        assert_eq!(
+            super::get_chain_reaction_counts(&graph),
+            vec![6, 0, 0, 0, 0, 1, 0]
+        );
+    }
+
+    #[test]
+    fn solve_2() {
+        assert_eq!(super::solve_2(EXAMPLE_INPUT), 7);
+    }
+}
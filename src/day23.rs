@@ -1,5 +1,7 @@
 use std::{collections::HashMap, vec};
 
+use crate::solver::{Options, Solution};
+
 #[derive(Debug, PartialEq, Clone, Copy, Eq)]
 enum Tile {
     UpSlope,
@@ -124,9 +126,8 @@ fn solve_1(trail: &Trail) -> usize
     longest_route + 1
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", solve_1(&parse(&input)));
-    println!("Part 2: {}", solve_1(&parse_for_part2(&input)));
+pub fn solve(input: &str, _: &Options) -> Result<Solution, String> {
+    Ok(vec![solve_1(&parse(input)).to_string(), solve_1(&parse_for_part2(input)).to_string()].into())
 }
 
 #[cfg(test)]
@@ -159,12 +160,12 @@ const EXAMPLE_INPUT: &str = "#.#####################
 
 #[test]
 fn solve_1() {
-    assert_eq!(super::solve_1(&parse(&EXAMPLE_INPUT)), 94);
+    assert_eq!(super::solve_1(&parse(EXAMPLE_INPUT)), 94);
 }
 
 #[test]
 fn solve_2() {
-    assert_eq!(super::solve_1(&parse_for_part2(&EXAMPLE_INPUT)), 154);
+    assert_eq!(super::solve_1(&parse_for_part2(EXAMPLE_INPUT)), 154);
 }
 
 
@@ -1,13 +1,15 @@
 use itertools::Itertools;
 
+use crate::solver::{Options, Solution};
+
 #[derive(Debug, Clone)]
 struct Hail {
     x: f64,
     y: f64,
-    _z: f64,
+    z: f64,
     vx: f64,
     vy: f64,
-    _vz: f64,
+    vz: f64,
 }
 
 fn parse(input: &str) -> Vec<Hail> {
@@ -21,11 +23,11 @@ fn parse(input: &str) -> Vec<Hail> {
             Hail {
                 x: pos_split.next().unwrap().trim().parse().unwrap(),
                 y: pos_split.next().unwrap().trim().parse().unwrap(),
-                _z: pos_split.next().unwrap().trim().parse().unwrap(),
+                z: pos_split.next().unwrap().trim().parse().unwrap(),
 
                 vx: vel_split.next().unwrap().trim().parse().unwrap(),
                 vy: vel_split.next().unwrap().trim().parse().unwrap(),
-                _vz: vel_split.next().unwrap().trim().parse().unwrap(),
+                vz: vel_split.next().unwrap().trim().parse().unwrap(),
             }
         })
         .collect()
@@ -54,13 +56,56 @@ fn calculate_intersection_2d(hail_1: &Hail, hail_2: &Hail) -> Option<(f64, f64)>
     }
 }
 
-fn solve_1(input: &str, limits: &(f64, f64)) -> usize {
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TestArea {
+    x: (f64, f64),
+    y: (f64, f64),
+    z: (f64, f64),
+}
+
+impl Default for TestArea {
+    fn default() -> Self {
+        TestArea {
+            x: (200000000000000.0, 400000000000000.0),
+            y: (200000000000000.0, 400000000000000.0),
+            z: (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+}
+
+fn is_within(value: f64, limits: (f64, f64)) -> bool {
+    value >= limits.0 && value <= limits.1
+}
+
+fn parse_limits(input: &str) -> Option<(f64, f64)> {
+    let (min, max) = input.split_once(',')?;
+    Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+}
+
+fn parse_test_area(options: &Options) -> Result<TestArea, String> {
+    let get_limits = |name: &str, default: (f64, f64)| match options.get(name)? {
+        Some(limits) => parse_limits(limits).ok_or(format!(
+            "invalid value {:?} for {}, expected min,max",
+            limits, name
+        )),
+        None => Ok(default),
+    };
+    let default = TestArea::default();
+
+    Ok(TestArea {
+        x: get_limits("--area-x", default.x)?,
+        y: get_limits("--area-y", default.y)?,
+        z: get_limits("--area-z", default.z)?,
+    })
+}
+
+fn solve_1(input: &str, area: &TestArea) -> usize {
     parse(input)
         .into_iter()
         .tuple_combinations()
         .filter(|(hail_1, hail_2)| {
-            if let Some((x, y)) = calculate_intersection_2d(&hail_1, &hail_2) {
-                x >= limits.0 && x <= limits.1 && y >= limits.0 && y <= limits.1
+            if let Some((x, y)) = calculate_intersection_2d(hail_1, hail_2) {
+                is_within(x, area.x) && is_within(y, area.y)
             } else {
                 false
             }
@@ -68,11 +113,105 @@ fn solve_1(input: &str, limits: &(f64, f64)) -> usize {
         .count()
 }
 
-pub fn solve(input: &str) {
-    println!(
-        "{}",
-        solve_1(&input, &(200000000000000.0, 400000000000000.0))
-    );
+fn calculate_closest_approach_3d(hail_1: &Hail, hail_2: &Hail) -> (f64, f64) {
+    let dp = [
+        hail_2.x - hail_1.x,
+        hail_2.y - hail_1.y,
+        hail_2.z - hail_1.z,
+    ];
+    let dv = [
+        hail_2.vx - hail_1.vx,
+        hail_2.vy - hail_1.vy,
+        hail_2.vz - hail_1.vz,
+    ];
+
+    let dot = |a: &[f64; 3], b: &[f64; 3]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+    let speed_squared = dot(&dv, &dv);
+    let time = if speed_squared == 0.0 {
+        0.0
+    } else {
+        (-dot(&dp, &dv) / speed_squared).max(0.0)
+    };
+
+    let offset = [0, 1, 2].map(|axis| dp[axis] + dv[axis] * time);
+    (time, dot(&offset, &offset).sqrt())
+}
+
+fn get_closest_pair_3d(hails: &[Hail]) -> Option<(usize, usize, f64, f64)> {
+    (0..hails.len())
+        .tuple_combinations()
+        .map(|(a, b)| {
+            let (time, distance) = calculate_closest_approach_3d(&hails[a], &hails[b]);
+            (a, b, time, distance)
+        })
+        .min_by(|x, y| x.3.total_cmp(&y.3))
+}
+
+fn calculate_collision_time(hail_1: &Hail, hail_2: &Hail) -> Option<f64> {
+    let axes = [
+        (hail_2.x - hail_1.x, hail_1.vx - hail_2.vx),
+        (hail_2.y - hail_1.y, hail_1.vy - hail_2.vy),
+        (hail_2.z - hail_1.z, hail_1.vz - hail_2.vz),
+    ]
+    .map(|(dp, dv)| (dp as i128, dv as i128));
+
+    let mut time: Option<(i128, i128)> = None;
+    for (dp, dv) in axes {
+        if dv == 0 {
+            if dp != 0 {
+                return None;
+            }
+            continue;
+        }
+
+        let (num, den) = if dv < 0 { (-dp, -dv) } else { (dp, dv) };
+        if num < 0 {
+            return None;
+        }
+        match time {
+            Some((t_num, t_den)) if t_num * den != num * t_den => return None,
+            Some(_) => (),
+            None => time = Some((num, den)),
+        }
+    }
+
+    Some(time.map_or(0.0, |(num, den)| num as f64 / den as f64))
+}
+
+fn get_collisions_3d(hails: &[Hail], area: &TestArea) -> Vec<(usize, usize, f64)> {
+    (0..hails.len())
+        .tuple_combinations()
+        .filter_map(|(a, b)| {
+            let time = calculate_collision_time(&hails[a], &hails[b])?;
+            let hail = &hails[a];
+            let inside = is_within(hail.x + hail.vx * time, area.x)
+                && is_within(hail.y + hail.vy * time, area.y)
+                && is_within(hail.z + hail.vz * time, area.z);
+            inside.then_some((a, b, time))
+        })
+        .collect()
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let area = parse_test_area(options)?;
+
+    let mut solution = Solution::from(vec![solve_1(input, &area).to_string()]);
+
+    if options.flag("--3d") {
+        let hails = parse(input);
+
+        if let Some((a, b, time, distance)) = get_closest_pair_3d(&hails) {
+            solution.note(format!(
+                "closest approach: hail {} and {} at t={} distance {}",
+                a, b, time, distance
+            ));
+        }
+        for (a, b, time) in get_collisions_3d(&hails, &area) {
+            solution.note(format!("collision: hail {} and {} at t={}", a, b, time));
+        }
+    }
+
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -85,8 +224,77 @@ mod tests {
 12, 31, 28 @ -1, -2, -1
 20, 19, 15 @  1, -5, -3";
 
+    fn get_example_area() -> TestArea {
+        TestArea {
+            x: (7.0, 27.0),
+            y: (7.0, 27.0),
+            z: (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+
     #[test]
     fn example() {
-        assert_eq!(solve_1(&EXAMPLE_INPUT, &(7.0, 27.0)), 2);
+        assert_eq!(solve_1(EXAMPLE_INPUT, &get_example_area()), 2);
+    }
+
+    #[test]
+    fn separate_axis_limits() {
+        let area = TestArea {
+            x: (0.0, 27.0),
+            ..get_example_area()
+        };
+        assert_eq!(solve_1(EXAMPLE_INPUT, &area), 3);
+    }
+
+    #[test]
+    fn parse_test_area() {
+        let options = Options::from([("--area-x", Some("7,27")), ("--area-z", Some("-5, 5"))]);
+        let area = super::parse_test_area(&options).unwrap();
+
+        assert_eq!(area.x, (7.0, 27.0));
+        assert_eq!(area.y, TestArea::default().y);
+        assert_eq!(area.z, (-5.0, 5.0));
+        assert!(super::parse_test_area(&Options::from([("--area-y", Some("7"))])).is_err());
+    }
+
+    #[test]
+    fn calculate_closest_approach_3d() {
+        let hails = parse("0, 0, 0 @ 1, 0, 0\n10, 5, 0 @ -1, 0, 0");
+        assert_eq!(
+            super::calculate_closest_approach_3d(&hails[0], &hails[1]),
+            (5.0, 5.0)
+        );
+
+        let hails = parse("0, 0, 0 @ 1, 0, 0\n-10, 3, 4 @ -1, 0, 0");
+        assert_eq!(
+            super::calculate_closest_approach_3d(&hails[0], &hails[1]),
+            (0.0, (125.0_f64).sqrt())
+        );
+    }
+
+    #[test]
+    fn get_collisions_3d() {
+        let hails = parse(
+            "0, 0, 0 @ 1, 1, 1
+4, 0, 4 @ -1, 1, -1
+0, 3, 0 @ 1, 0, 1
+10, 10, 10 @ 1, 1, 1",
+        );
+
+        assert_eq!(
+            super::get_collisions_3d(&hails, &TestArea::default()),
+            vec![]
+        );
+        assert_eq!(
+            super::get_collisions_3d(
+                &hails,
+                &TestArea {
+                    x: (0.0, 100.0),
+                    y: (0.0, 100.0),
+                    z: (0.0, 100.0),
+                }
+            ),
+            vec![(0, 1, 2.0), (0, 2, 3.0)]
+        );
     }
 }
@@ -0,0 +1,221 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::solver::{Options, Solution};
+
+#[derive(Debug, PartialEq, Eq, Default)]
+struct Graph {
+    names: Vec<String>,
+    edges: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Cut {
+    component_sizes: (usize, usize),
+    edges: Vec<(String, String)>,
+}
+
+impl Graph {
+    fn get_or_insert(&mut self, indices: &mut HashMap<String, usize>, name: &str) -> usize {
+        *indices.entry(name.to_string()).or_insert_with(|| {
+            self.names.push(name.to_string());
+            self.edges.push(vec![]);
+            self.names.len() - 1
+        })
+    }
+}
+
+fn parse(input: &str) -> Graph {
+    let mut graph = Graph::default();
+    let mut indices = HashMap::new();
+
+    for line in input.lines() {
+        let (name, neighbors) = line.split_once(": ").unwrap();
+        let from = graph.get_or_insert(&mut indices, name);
+
+        for neighbor in neighbors.split_ascii_whitespace() {
+            let to = graph.get_or_insert(&mut indices, neighbor);
+            graph.edges[from].push(to);
+            graph.edges[to].push(from);
+        }
+    }
+
+    graph
+}
+
+fn find_augmenting_path(
+    graph: &Graph,
+    flow: &HashMap<(usize, usize), i32>,
+    source: usize,
+    sink: usize,
+) -> Result<Vec<usize>, HashSet<usize>> {
+    let mut previous = HashMap::from([(source, source)]);
+    let mut queue = VecDeque::from([source]);
+
+    while let Some(node) = queue.pop_front() {
+        if node == sink {
+            let mut path = vec![sink];
+            let mut current = sink;
+            while current != source {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Ok(path);
+        }
+
+        for &neighbor in &graph.edges[node] {
+            let residual = 1 - flow.get(&(node, neighbor)).unwrap_or(&0);
+            if residual > 0 && !previous.contains_key(&neighbor) {
+                previous.insert(neighbor, node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    Err(previous.into_keys().collect())
+}
+
+fn find_cut_between(
+    graph: &Graph,
+    source: usize,
+    sink: usize,
+    max_cut: usize,
+) -> Result<Option<HashSet<usize>>, String> {
+    let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+
+    for paths in 0..=max_cut {
+        match find_augmenting_path(graph, &flow, source, sink) {
+            Ok(path) => {
+                for step in path.windows(2) {
+                    *flow.entry((step[0], step[1])).or_default() += 1;
+                    *flow.entry((step[1], step[0])).or_default() -= 1;
+                }
+            }
+            Err(source_side) if paths == max_cut => return Ok(Some(source_side)),
+            Err(_) => {
+                return Err(format!(
+                    "{} and {} are separated by {} edges, expected {}",
+                    graph.names[source], graph.names[sink], paths, max_cut
+                ))
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn find_min_cut(graph: &Graph, cut_size: usize) -> Result<Cut, String> {
+    // every sink is checked, so a vertex cut off by fewer edges is reported
+    let mut source_side = None;
+    for sink in 1..graph.names.len() {
+        let cut = find_cut_between(graph, 0, sink, cut_size)?;
+        source_side = source_side.or(cut);
+    }
+    let source_side = source_side.ok_or("no three-edge cut found")?;
+
+    let mut edges: Vec<_> = source_side
+        .iter()
+        .flat_map(|&from| {
+            graph.edges[from]
+                .iter()
+                .filter(|to| !source_side.contains(to))
+                .map(move |&to| {
+                    let mut edge = [graph.names[from].clone(), graph.names[to].clone()];
+                    edge.sort();
+                    let [a, b] = edge;
+                    (a, b)
+                })
+        })
+        .collect();
+    edges.sort();
+
+    Ok(Cut {
+        component_sizes: (source_side.len(), graph.names.len() - source_side.len()),
+        edges,
+    })
+}
+
+pub fn solve(input: &str, options: &Options) -> Result<Solution, String> {
+    let cut = find_min_cut(&parse(input), 3)?;
+
+    let mut solution = Solution::from(vec![
+        (cut.component_sizes.0 * cut.component_sizes.1).to_string()
+    ]);
+    if options.flag("--edges") {
+        for (from, to) in &cut.edges {
+            solution.note(format!("{}/{}", from, to));
+        }
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::Options;
+
+    const EXAMPLE_INPUT: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn parse() {
+        let graph = super::parse("a: b c\nb: c");
+
+        assert_eq!(graph.names, vec!["a", "b", "c"]);
+        assert_eq!(graph.edges, vec![vec![1, 2], vec![0, 2], vec![0, 1]]);
+    }
+
+    #[test]
+    fn find_min_cut() {
+        let cut = super::find_min_cut(&super::parse(EXAMPLE_INPUT), 3).unwrap();
+
+        assert_eq!(cut.component_sizes.0 * cut.component_sizes.1, 54);
+        assert_eq!(
+            cut.edges,
+            vec![
+                ("bvb".to_string(), "cmg".to_string()),
+                ("hfx".to_string(), "pzl".to_string()),
+                ("jqt".to_string(), "nvd".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn solve() {
+        let options = Options::from([("--edges", None)]);
+
+        assert_eq!(
+            super::solve(EXAMPLE_INPUT, &Options::default()),
+            Ok(vec!["54".to_string()].into())
+        );
+        assert_eq!(
+            super::solve(EXAMPLE_INPUT, &options).unwrap().notes,
+            vec!["bvb/cmg", "hfx/pzl", "jqt/nvd"]
+        );
+        assert_eq!(
+            super::solve("a: b c d e\nb: c d e\nc: d e\nd: e", &options),
+            Err("no three-edge cut found".to_string())
+        );
+    }
+
+    #[test]
+    fn solve_rejects_smaller_cuts() {
+        assert_eq!(
+            super::solve("a: b\nb: c\nc: d", &Options::from([("--edges", None)])),
+            Err("a and b are separated by 1 edges, expected 3".to_string())
+        );
+        assert!(
+            super::solve(&format!("{}\nzzz: jqt", EXAMPLE_INPUT), &Options::default()).is_err()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::input::{get_answers_path, Source};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<Option<String>>,
+}
+
+#[derive(Debug)]
+pub enum ExampleError {
+    NoExamples(PathBuf),
+    Io(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExampleError::NoExamples(path) => {
+                write!(f, "no <pre><code> blocks found in {}", path.display())
+            }
+            ExampleError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ExampleError {}
+
+fn decode_html(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut in_tag = false;
+
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => decoded.push(ch),
+            _ => {}
+        }
+    }
+
+    decoded
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn find_between<'a>(text: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find(open) {
+        let content_start = offset + start + open.len();
+        let Some(end) = text[content_start..].find(close) else {
+            break;
+        };
+        found.push((offset + start, &text[content_start..content_start + end]));
+        offset = content_start + end + close.len();
+    }
+
+    found
+}
+
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let articles = find_between(html, "<article", "</article>");
+
+    for (part, (_, article)) in articles.iter().enumerate() {
+        for (_, block) in find_between(article, "<pre><code>", "</code></pre>") {
+            examples.push(Example {
+                input: decode_html(block),
+                answers: vec![],
+            });
+        }
+
+        let answer = find_between(article, "<code><em>", "</em></code>")
+            .into_iter()
+            .chain(find_between(article, "<em><code>", "</code></em>"))
+            .max_by_key(|&(position, _)| position)
+            .map(|(_, answer)| decode_html(answer));
+
+        if let (Some(answer), Some(example)) = (answer, examples.last_mut()) {
+            example.answers.resize(part, None);
+            example.answers.push(Some(answer));
+        }
+    }
+
+    examples
+}
+
+pub fn write(day: u32, examples: &[Example]) -> Result<Vec<PathBuf>, ExampleError> {
+    let mut written = vec![];
+
+    for (idx, example) in examples.iter().enumerate() {
+        let path = Source::Example(idx as u32 + 1).path(day).unwrap();
+        let answers: String = example
+            .answers
+            .iter()
+            .map(|answer| answer.clone().unwrap_or_default() + "\n")
+            .collect();
+
+        for (path, content) in [
+            (path.clone(), &example.input),
+            (get_answers_path(&path), &answers),
+        ] {
+            path.parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, content))
+                .map_err(|error| ExampleError::Io(path.clone(), error))?;
+        }
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+pub fn extract_from_file(day: u32, html_path: &Path) -> Result<Vec<PathBuf>, ExampleError> {
+    let html = std::fs::read_to_string(html_path)
+        .map_err(|error| ExampleError::Io(html_path.to_path_buf(), error))?;
+
+    match extract(&html) {
+        examples if examples.is_empty() => Err(ExampleError::NoExamples(html_path.to_path_buf())),
+        examples => write(day, &examples),
+    }
+}
+
+pub fn list(day: u32) -> Vec<u32> {
+    (1..)
+        .map_while(|number| {
+            Source::Example(number)
+                .path(day)
+                .filter(|path| path.exists())
+                .map(|_| number)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE_HTML: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Here, the calibration values are <code>12</code> and <code>38</code>.
+Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54239</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+a&lt;b&gt;<em>c</em>
+</code></pre>
+<p>Adding these together produces <em><code>29</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn decode_html() {
+        assert_eq!(
+            super::decode_html("<em>a</em> &lt;b&gt; &amp;lt;"),
+            "a <b> &lt;"
+        );
+    }
+
+    #[test]
+    fn extract() {
+        assert_eq!(
+            super::extract(PUZZLE_HTML),
+            vec![
+                Example {
+                    input: "1abc2\npqr3stu8vwx\n".to_string(),
+                    answers: vec![Some("50".to_string())],
+                },
+                Example {
+                    input: "two1nine\na<b>c\n".to_string(),
+                    answers: vec![None, Some("29".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_shared_example() {
+        let html = PUZZLE_HTML.replace(
+            "<pre><code>two1nine\na&lt;b&gt;<em>c</em>\n</code></pre>",
+            "",
+        );
+
+        assert_eq!(
+            super::extract(&html),
+            vec![Example {
+                input: "1abc2\npqr3stu8vwx\n".to_string(),
+                answers: vec![Some("50".to_string()), Some("29".to_string())],
+            }]
+        );
+    }
+}
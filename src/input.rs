@@ -0,0 +1,242 @@
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::client::{Client, ClientError};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Source {
+    Default,
+    Path(PathBuf),
+    Stdin,
+    Example(u32),
+    Set(String),
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub path: String,
+    pub error: std::io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error.kind() {
+            std::io::ErrorKind::NotFound => write!(f, "no input found at {}", self.path),
+            _ => write!(f, "could not read {}: {}", self.path, self.error),
+        }
+    }
+}
+
+impl InputError {
+    pub fn is_missing(&self) -> bool {
+        self.error.kind() == std::io::ErrorKind::NotFound
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn get_default_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("inputs/day{day:02}.txt"))
+}
+
+pub fn fingerprint(input: &str) -> String {
+    format!("{:x}", md5::compute(input))
+}
+
+pub fn get_answers_path(input_path: &Path) -> PathBuf {
+    input_path.with_extension("answers")
+}
+
+pub fn read_answers(input_path: &Path) -> Vec<Option<String>> {
+    std::fs::read_to_string(get_answers_path(input_path))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| Some(line.trim().to_string()).filter(|answer| !answer.is_empty()))
+        .collect()
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "input"),
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+            Source::Example(number) => write!(f, "example {}", number),
+            Source::Set(name) => write!(f, "set {}", name),
+        }
+    }
+}
+
+impl Source {
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        match self {
+            Source::Default => Some(get_default_path(day)),
+            Source::Path(path) => Some(path.clone()),
+            Source::Stdin => None,
+            Source::Example(number) => {
+                Some(PathBuf::from(format!("examples/day{day:02}/{number}.txt")))
+            }
+            Source::Set(name) => Some(PathBuf::from(format!("inputs/{name}/day{day:02}.txt"))),
+        }
+    }
+
+    pub fn load(&self, day: u32) -> Result<String, InputError> {
+        let mut input = String::new();
+
+        match self.path(day) {
+            Some(path) => std::fs::File::open(&path)
+                .and_then(|mut file| file.read_to_string(&mut input))
+                .map_err(|error| InputError {
+                    path: path.display().to_string(),
+                    error,
+                })?,
+            None => std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| InputError {
+                    path: "<stdin>".to_string(),
+                    error,
+                })?,
+        };
+
+        Ok(input)
+    }
+}
+
+pub fn list_sets() -> Vec<String> {
+    let mut sets: Vec<_> = std::fs::read_dir("inputs")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    sets.sort();
+    sets
+}
+
+pub fn fetch(day: u32, client: &Client, path: &Path) -> Result<String, ClientError> {
+    let input = client.get(&format!("{}/input", client.day_url(day)))?;
+
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, &input))
+        .map_err(|error| ClientError::Io(path.to_path_buf(), error))?;
+
+    Ok(input)
+}
+
+pub fn parse_source(args: &[String]) -> Result<Source, String> {
+    let get_arg = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .map(|idx| args.get(idx + 1).ok_or(format!("{} needs a value", name)))
+            .transpose()
+    };
+
+    match (
+        get_arg("--input")?,
+        get_arg("--example")?,
+        get_arg("--set")?,
+    ) {
+        (None, None, None) => Ok(Source::Default),
+        (Some(path), None, None) if path == "-" => Ok(Source::Stdin),
+        (Some(path), None, None) => Ok(Source::Path(PathBuf::from(path))),
+        (None, Some(number), None) => number
+            .parse()
+            .map(Source::Example)
+            .map_err(|_| format!("invalid example number {:?}", number)),
+        (None, None, Some(name)) => Ok(Source::Set(name.clone())),
+        _ => Err("--input, --example and --set are mutually exclusive".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_source() {
+        assert_eq!(
+            super::parse_source(&args(&["aoc23", "3"])),
+            Ok(Source::Default)
+        );
+        assert_eq!(
+            super::parse_source(&args(&["aoc23", "3", "--input", "-"])),
+            Ok(Source::Stdin)
+        );
+        assert_eq!(
+            super::parse_source(&args(&["aoc23", "3", "--input", "my.txt"])),
+            Ok(Source::Path(PathBuf::from("my.txt")))
+        );
+        assert_eq!(
+            super::parse_source(&args(&["aoc23", "3", "--example", "2"])),
+            Ok(Source::Example(2))
+        );
+        assert_eq!(
+            super::parse_source(&args(&["aoc23", "3", "--set", "alice"])),
+            Ok(Source::Set("alice".to_string()))
+        );
+        assert!(super::parse_source(&args(&["aoc23", "--example", "x"])).is_err());
+        assert!(super::parse_source(&args(&["aoc23", "--example", "1", "--set", "bob"])).is_err());
+        assert!(super::parse_source(&args(&["aoc23", "--input"])).is_err());
+    }
+
+    #[test]
+    fn source_path() {
+        assert_eq!(
+            Source::Default.path(3),
+            Some(PathBuf::from("inputs/day03.txt"))
+        );
+        assert_eq!(
+            Source::Example(2).path(13),
+            Some(PathBuf::from("examples/day13/2.txt"))
+        );
+        assert_eq!(
+            Source::Set("bob".to_string()).path(8),
+            Some(PathBuf::from("inputs/bob/day08.txt"))
+        );
+        assert_eq!(Source::Stdin.path(1), None);
+    }
+
+    #[test]
+    fn fingerprint() {
+        assert_eq!(super::fingerprint(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(super::fingerprint("1abc2"), super::fingerprint("1abc2"));
+        assert_ne!(super::fingerprint("1abc2"), super::fingerprint("1abc3"));
+    }
+
+    #[test]
+    fn get_answers_path() {
+        assert_eq!(
+            super::get_answers_path(Path::new("examples/day03/1.txt")),
+            PathBuf::from("examples/day03/1.answers")
+        );
+    }
+
+    #[test]
+    fn missing_input_names_path() {
+        let error = Source::Path(PathBuf::from("does/not/exist.txt"))
+            .load(1)
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "no input found at does/not/exist.txt");
+        assert!(error.is_missing());
+    }
+
+    #[test]
+    fn fetch_stores_input() {
+        let (base_url, server) = stub::serve(vec![(200, "0 3 6\n".to_string())]);
+        let client = stub::get_test_client(&base_url, "fetch");
+        let path = client.state_dir.join("inputs/day09.txt");
+
+        assert_eq!(super::fetch(9, &client, &path).unwrap(), "0 3 6\n");
+        assert_eq!(Source::Path(path).load(9).unwrap(), "0 3 6\n");
+        assert!(server.join().unwrap()[0].starts_with("GET /2023/day/9/input "));
+    }
+}
@@ -1,33 +1,750 @@
-use std::io::Read;
+use cache::Cache;
+use client::Client;
+use input::Source;
+use report::{Format, Row, Status};
+use solver::{Options, Solver};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use worker::WorkerError;
 
-//mod day01;
-//mod day02;
-//mod day06;
+type CommandResult = Result<(), Box<dyn std::error::Error>>;
+
+const USAGE: &str =
+    "usage: aoc23 [day] [--input <path>|--example N|--set <name>] [--format json|csv|table]
+       aoc23 fetch|verify|cross|bench [day]
+       aoc23 new <day>
+       aoc23 examples <day> [page.html]
+       aoc23 submit <day> <part>";
+
+mod cache;
+mod client;
+
+#[cfg(test)]
+mod day00;
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day06;
 //mod day07;
-// mod day08;
-//mod day09;
+mod day08;
+mod day09;
 //mod day10;
 mod day11;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day19;
+mod day20;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+mod examples;
+mod input;
+mod report;
+mod scaffold;
+mod solver;
+mod submit;
+mod verify;
+mod worker;
+
+type Day = (u32, Solver, &'static str);
 
-fn run(day: u32, solve_fn: fn(&str)) {
-    let mut input_file =
-        std::fs::File::open(format!("inputs/day{day:02}.txt")).expect("Could not open file");
-    let mut input = String::new();
-    input_file
-        .read_to_string(&mut input)
-        .expect("Reading file failed!");
+const DAYS: &[Day] = &[
+    (1, day01::solve, include_str!("day01.rs")),
+    (2, day02::solve, include_str!("day02.rs")),
+    (3, day03::solve, include_str!("day03.rs")),
+    (4, day04::solve, include_str!("day04.rs")),
+    (6, day06::solve, include_str!("day06.rs")),
+    //(7, day07::solve, include_str!("day07.rs")),
+    (8, day08::solve, include_str!("day08.rs")),
+    (9, day09::solve, include_str!("day09.rs")),
+    //(10, day10::solve, include_str!("day10.rs")),
+    (11, day11::solve, include_str!("day11.rs")),
+    (13, day13::solve, include_str!("day13.rs")),
+    (14, day14::solve, include_str!("day14.rs")),
+    (15, day15::solve, include_str!("day15.rs")),
+    (16, day16::solve, include_str!("day16.rs")),
+    (19, day19::solve, include_str!("day19.rs")),
+    (20, day20::solve, include_str!("day20.rs")),
+    (22, day22::solve, include_str!("day22.rs")),
+    (23, day23::solve, include_str!("day23.rs")),
+    (24, day24::solve, include_str!("day24.rs")),
+    (25, day25::solve, include_str!("day25.rs")),
+];
+
+const SLOW_DAYS: [u32; 3] = [14, 16, 23];
+
+struct Context {
+    client: Client,
+    cache: Mutex<Cache>,
+    force: bool,
+    timeout: Duration,
+    jobs: usize,
+    options: Options,
+}
 
-    solve_fn(input.trim());
+struct Run {
+    answers: Vec<String>,
+    notes: Vec<String>,
+    duration: Duration,
+    fingerprint: String,
+    cached: bool,
+}
+
+fn load(day: u32, source: &Source, client: &Client) -> Result<String, Box<dyn std::error::Error>> {
+    let input = match source.load(day) {
+        Err(error)
+            if *source == Source::Default && error.is_missing() && client.session.is_some() =>
+        {
+            input::fetch(day, client, &input::get_default_path(day))?
+        }
+        Ok(input)
+            if *source == Source::Default
+                && input.trim().is_empty()
+                && client.session.is_some() =>
+        {
+            input::fetch(day, client, &input::get_default_path(day))?
+        }
+        result => result?,
+    };
+    Ok(input)
+}
+
+fn solve(solve_fn: Solver, input: &str, options: &Options) -> Result<Run, String> {
+    let start = Instant::now();
+    let solution = solve_fn(input.trim(), options)?;
+
+    Ok(Run {
+        answers: solution.answers,
+        notes: solution.notes,
+        duration: start.elapsed(),
+        fingerprint: input::fingerprint(input),
+        cached: false,
+    })
+}
+
+fn run(
+    &(day, solve_fn, code): &Day,
+    source: &Source,
+    context: &Context,
+) -> Result<Run, Box<dyn std::error::Error>> {
+    let input = load(day, source, &context.client)?;
+    let fingerprint = input::fingerprint(&input);
+    let version = input::fingerprint(code);
+    let cacheable = context.options.is_empty();
+
+    if cacheable && SLOW_DAYS.contains(&day) && !context.force {
+        if let Some(answers) = context
+            .cache
+            .lock()
+            .unwrap()
+            .get(day, &fingerprint, &version)
+        {
+            return Ok(Run {
+                answers,
+                notes: vec![],
+                duration: Duration::ZERO,
+                fingerprint,
+                cached: true,
+            });
+        }
+    }
+
+    let options = context.options.clone();
+    let run = worker::run_isolated(move || solve(solve_fn, &input, &options), context.timeout)??;
+    if !cacheable {
+        return Ok(run);
+    }
+    if let Err(error) =
+        context
+            .cache
+            .lock()
+            .unwrap()
+            .store(day, &fingerprint, &version, &run.answers)
+    {
+        eprintln!("could not update the result cache: {}", error);
+    }
+    Ok(run)
+}
+
+fn get_error_row(day: u32, source: &Source, error: &(dyn std::error::Error + 'static)) -> Row {
+    Row {
+        day,
+        part: None,
+        source: source.to_string(),
+        answer: None,
+        status: match error.downcast_ref::<WorkerError>() {
+            Some(WorkerError::Timeout(_)) => Status::Timeout,
+            Some(WorkerError::Panic(_)) => Status::Panic,
+            None => Status::Error,
+        },
+        message: Some(error.to_string()),
+        duration: Duration::ZERO,
+        fingerprint: None,
+    }
+}
+
+fn print_notes(results: Vec<(Vec<Row>, Vec<String>)>) -> Vec<Row> {
+    let mut rows = vec![];
+    for (day_rows, notes) in results {
+        for note in notes {
+            eprintln!("{}", note);
+        }
+        rows.extend(day_rows);
+    }
+    rows
+}
+
+fn get_answer_rows(day: u32, source: &Source, run: &Run) -> Vec<Row> {
+    run.answers
+        .iter()
+        .enumerate()
+        .map(|(idx, answer)| Row {
+            day,
+            part: Some(idx + 1),
+            source: source.to_string(),
+            answer: Some(answer.clone()),
+            status: Status::Ok,
+            message: run.cached.then(|| "cached".to_string()),
+            duration: run.duration,
+            fingerprint: Some(run.fingerprint.clone()),
+        })
+        .collect()
+}
+
+fn get_check_rows(day: u32, source: &Source, run: &Run) -> Vec<Row> {
+    verify::check(day, source, &run.answers)
+        .into_iter()
+        .map(|check| Row {
+            day,
+            part: Some(check.part),
+            source: source.to_string(),
+            status: match check.passed() {
+                true => Status::Ok,
+                false => Status::Fail,
+            },
+            answer: check.actual,
+            message: Some(match run.cached {
+                true => format!("expected {} (cached)", check.expected),
+                false => format!("expected {}", check.expected),
+            }),
+            duration: run.duration,
+            fingerprint: Some(run.fingerprint.clone()),
+        })
+        .collect()
+}
+
+fn report(rows: &[Row], format: Option<Format>, print_plain: fn(&Row)) -> CommandResult {
+    match format {
+        Some(format) => print!("{}", report::render(rows, format)),
+        None => rows.iter().for_each(print_plain),
+    }
+
+    get_result(rows)
+}
+
+fn get_result(rows: &[Row]) -> CommandResult {
+    match rows
+        .iter()
+        .filter(|row| !matches!(row.status, Status::Ok | Status::Unchecked))
+        .count()
+    {
+        0 => Ok(()),
+        failed => Err(format!("{} of {} results failed", failed, rows.len()).into()),
+    }
+}
+
+fn select_days(arg: Option<&String>) -> Result<Vec<Day>, String> {
+    match arg.map(|arg| (arg, arg.parse::<u32>())) {
+        None => Ok(DAYS.to_vec()),
+        Some((arg, _)) if arg.starts_with("--") => Ok(DAYS.to_vec()),
+        Some((_, Ok(day))) => get_day(day)
+            .map(|day| vec![day])
+            .ok_or(format!("day {} is not registered", day)),
+        Some((arg, Err(_))) => Err(format!("unknown command or day {:?}\n{}", arg, USAGE)),
+    }
+}
+
+fn get_day(day: u32) -> Option<Day> {
+    DAYS.iter()
+        .find(|(registered, _, _)| *registered == day)
+        .copied()
+}
+
+fn fetch(days: &[Day], client: &Client) -> CommandResult {
+    let mut failed = 0;
+    for &(day, _, _) in days {
+        let path = input::get_default_path(day);
+        if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            continue;
+        }
+        match input::fetch(day, client, &path) {
+            Ok(_) => println!("day {:02}: fetched {}", day, path.display()),
+            Err(error) => {
+                eprintln!("day {:02}: {}", day, error);
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} inputs could not be fetched", failed).into()),
+    }
+}
+
+fn new_day(args: &[String]) -> CommandResult {
+    let day = args
+        .get(2)
+        .and_then(|arg| arg.parse().ok())
+        .ok_or("usage: aoc23 new <day>")?;
+
+    for path in scaffold::new_day(
+        day,
+        &input::get_default_path(day),
+        &Source::Example(1).path(day).unwrap(),
+    )? {
+        println!("created {}", path.display());
+    }
+    Ok(())
+}
+
+fn extract_examples(args: &[String]) -> CommandResult {
+    let day: u32 = args
+        .get(2)
+        .and_then(|arg| arg.parse().ok())
+        .ok_or("usage: aoc23 examples <day> [page.html]")?;
+    let html_path = args
+        .get(3)
+        .map_or(format!("puzzles/day{day:02}.html"), |path| path.clone());
+
+    for path in examples::extract_from_file(day, std::path::Path::new(&html_path))? {
+        let answers = input::read_answers(&path);
+        println!("wrote {} {:?}", path.display(), answers);
+    }
+    Ok(())
+}
+
+fn submit(args: &[String], source: &Source, context: &Context) -> CommandResult {
+    let get_number = |idx: usize| args.get(idx).and_then(|arg| arg.parse::<u32>().ok());
+    let (Some(day), Some(part @ 1..=2)) = (get_number(2), get_number(3)) else {
+        return Err("usage: aoc23 submit <day> <part>".into());
+    };
+    let registered = get_day(day).ok_or(format!("day {} is not registered", day))?;
+
+    let answers = run(&registered, source, context)?.answers;
+    let answer = answers
+        .get(part as usize - 1)
+        .ok_or(format!("day {} has no answer for part {}", day, part))?;
+    println!("day {:02} part {}: {}", day, part, answer);
+
+    let client = &context.client;
+    let mut history = submit::History::load(client.state_dir.join("submissions.tsv"))?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    println!(
+        "{}",
+        submit::submit(client, &mut history, (day, part), answer, now)?
+    );
+    Ok(())
+}
+
+fn get_unchecked_row(day: u32) -> Row {
+    Row {
+        day,
+        part: None,
+        source: "examples".to_string(),
+        answer: None,
+        status: Status::Unchecked,
+        message: Some("no examples with answers".to_string()),
+        duration: Duration::ZERO,
+        fingerprint: None,
+    }
+}
+
+fn get_verify_rows(days: &[Day], sources: fn(u32) -> Vec<Source>, context: &Context) -> Vec<Row> {
+    let runs: Vec<_> = days
+        .iter()
+        .flat_map(|&day| match sources(day.0) {
+            sources if sources.is_empty() => vec![(day, None)],
+            sources => sources
+                .into_iter()
+                .map(|source| (day, Some(source)))
+                .collect(),
+        })
+        .collect();
+
+    let results = worker::map_parallel(
+        &runs,
+        context.jobs,
+        |(registered @ (day, _, _), source)| match source {
+            None => (vec![get_unchecked_row(*day)], vec![]),
+            Some(source) => match run(registered, source, context) {
+                Ok(run) => (get_check_rows(*day, source, &run), run.notes),
+                Err(error) => (vec![get_error_row(*day, source, error.as_ref())], vec![]),
+            },
+        },
+    );
+    print_notes(results)
+}
+
+fn get_cross_rows(days: &[Day], sets: &[String], context: &Context) -> Vec<Row> {
+    let runs: Vec<_> = days
+        .iter()
+        .flat_map(|&day| sets.iter().map(move |set| (day, Source::Set(set.clone()))))
+        .filter(|((day, _, _), source)| source.path(*day).is_some_and(|path| path.exists()))
+        .collect();
+
+    let results = worker::map_parallel(
+        &runs,
+        context.jobs,
+        |(registered @ (day, _, _), source)| match run(registered, source, context) {
+            Ok(run) => {
+                let mut checked = get_check_rows(*day, source, &run);
+                let unchecked: Vec<_> = get_answer_rows(*day, source, &run)
+                    .into_iter()
+                    .filter(|row| checked.iter().all(|check| check.part != row.part))
+                    .map(|row| Row {
+                        status: Status::Unchecked,
+                        ..row
+                    })
+                    .collect();
+                checked.extend(unchecked);
+                checked.sort_by_key(|row| row.part);
+                (checked, run.notes)
+            }
+            Err(error) => (vec![get_error_row(*day, source, error.as_ref())], vec![]),
+        },
+    );
+    print_notes(results)
+}
+
+fn cross(days: &[Day], format: Option<Format>, context: &Context) -> CommandResult {
+    let sets = input::list_sets();
+    if sets.is_empty() {
+        return Err("no input sets found, expected inputs/<name>/dayNN.txt".into());
+    }
+
+    let rows = get_cross_rows(days, &sets, context);
+    match format {
+        Some(format) => print!("{}", report::render(&rows, format)),
+        None => {
+            let sources: Vec<_> = sets
+                .iter()
+                .map(|set| Source::Set(set.clone()).to_string())
+                .collect();
+            print!("{}", report::render_matrix(&rows, &sources));
+        }
+    }
+
+    get_result(&rows)
+}
+
+fn print_check(row: &Row) {
+    let answer = row.answer.as_deref().unwrap_or("nothing");
+    let message = row.message.as_deref().unwrap_or_default();
+
+    match row.status {
+        Status::Unchecked if row.part.is_none() => println!("day {:02}: {}", row.day, message),
+        Status::Ok | Status::Unchecked => println!(
+            "day {:02} {} part {}: ok {}",
+            row.day,
+            row.source,
+            row.part.unwrap(),
+            answer
+        ),
+        Status::Fail => println!(
+            "day {:02} {} part {}: FAIL {}, got {}",
+            row.day,
+            row.source,
+            row.part.unwrap(),
+            message,
+            answer
+        ),
+        Status::Error | Status::Timeout | Status::Panic => {
+            eprintln!("day {:02} {}: {}", row.day, row.source, message)
+        }
+    }
+}
+
+fn verify(days: &[Day], format: Option<Format>, context: &Context) -> CommandResult {
+    report(
+        &get_verify_rows(days, verify::get_sources, context),
+        format,
+        print_check,
+    )
+}
+
+fn print_answer(row: &Row) {
+    match &row.answer {
+        Some(answer) => println!("{}", answer),
+        None => eprintln!(
+            "day {:02}: {}",
+            row.day,
+            row.message.as_deref().unwrap_or_default()
+        ),
+    }
+}
+
+fn run_all(
+    days: &[Day],
+    source: &Source,
+    format: Option<Format>,
+    context: &Context,
+) -> CommandResult {
+    let results = worker::map_parallel(days, context.jobs, |registered @ &(day, _, _)| {
+        match run(registered, source, context) {
+            Ok(run) => (get_answer_rows(day, source, &run), run.notes),
+            Err(error) => (vec![get_error_row(day, source, error.as_ref())], vec![]),
+        }
+    });
+    let rows = print_notes(results);
+
+    report(&rows, format, print_answer)
+}
+
+fn get_median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+fn bench(days: &[Day], args: &[String], format: Option<Format>, client: &Client) -> CommandResult {
+    let runs: usize = match args.iter().position(|arg| arg == "--runs") {
+        Some(idx) => args
+            .get(idx + 1)
+            .and_then(|runs| runs.parse().ok())
+            .filter(|&runs| runs > 0)
+            .ok_or("--runs needs a positive number")?,
+        None => 5,
+    };
+
+    let mut rows = vec![];
+    for &(day, solve_fn, _) in days {
+        let runs = match load(day, &Source::Default, client).and_then(|input| {
+            (0..runs)
+                .map(|_| solve(solve_fn, &input, &Options::default()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| error.into())
+        }) {
+            Ok(runs) => runs,
+            Err(error) => {
+                rows.push(get_error_row(day, &Source::Default, error.as_ref()));
+                continue;
+            }
+        };
+
+        let median = get_median(runs.iter().map(|run| run.duration).collect());
+        rows.extend(
+            get_answer_rows(day, &Source::Default, &runs[0])
+                .into_iter()
+                .map(|row| Row {
+                    duration: median,
+                    ..row
+                }),
+        );
+    }
+
+    report(&rows, Some(format.unwrap_or(Format::Table)), print_answer)
 }
 
 fn main() {
-    //run(1, day01::solve);
-    //run(2, day02::solve);
-    //run(4, day04::solve);
-    //run(6, day06::solve);
-    //run(7, day07::solve);
-    //run(8, day08::solve);
-    //run(9, day09::solve);
-    //run(10, day10::solve);
-    run(11, day11::solve);
+    let args: Vec<_> = std::env::args().collect();
+
+    let result = input::parse_source(&args)
+        .and_then(|source| {
+            let format = report::parse_format(&args)?;
+            let timeout = worker::parse_timeout(&args)?;
+            Ok((source, format, timeout, worker::parse_jobs(&args)?))
+        })
+        .map_err(|error| error.into())
+        .and_then(|(source, format, timeout, jobs)| {
+            let options = Options::parse(&args);
+            let single_day = args.get(1).is_some_and(|arg| arg.parse::<u32>().is_ok());
+            if !options.is_empty() && !single_day {
+                return Err(format!(
+                    "{} only applies when running a single day, e.g. `aoc23 <day> {}`",
+                    options.names().join(" "),
+                    options.names()[0]
+                )
+                .into());
+            }
+
+            let client = Client::from_env();
+            let context = Context {
+                cache: Mutex::new(Cache::load(client.state_dir.join("results.tsv"))),
+                force: args.iter().any(|arg| arg == "--force"),
+                timeout,
+                jobs,
+                options,
+                client,
+            };
+
+            match args.get(1).map(String::as_str) {
+                Some("fetch") => fetch(&select_days(args.get(2))?, &context.client),
+                Some("new") => new_day(&args),
+                Some("examples") => extract_examples(&args),
+                Some("submit") => submit(&args, &source, &context),
+                Some("verify") => verify(&select_days(args.get(2))?, format, &context),
+                Some("cross") => cross(&select_days(args.get(2))?, format, &context),
+                Some("bench") => bench(&select_days(args.get(2))?, &args, format, &context.client),
+                day if source != Source::Default
+                    && day.and_then(|day| day.parse::<u32>().ok()).is_none() =>
+                {
+                    Err(
+                        "--input, --example and --set need a day, e.g. `aoc23 3 --example 1`"
+                            .into(),
+                    )
+                }
+                _ => run_all(&select_days(args.get(1))?, &source, format, &context),
+            }
+        });
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_context(name: &str) -> Context {
+        let client = Client::from_env();
+        Context {
+            client,
+            cache: Mutex::new(Cache::load(
+                client::stub::get_temp_dir(name).join("results.tsv"),
+            )),
+            force: false,
+            timeout: Duration::from_secs(60),
+            jobs: 4,
+            options: Options::default(),
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let failed: Vec<_> = get_verify_rows(
+            DAYS,
+            |day| {
+                examples::list(day)
+                    .into_iter()
+                    .map(Source::Example)
+                    .collect()
+            },
+            &get_test_context("examples"),
+        )
+        .into_iter()
+        .filter(|row| row.status != Status::Ok)
+        .map(|row| format!("{:?}", row))
+        .collect();
+
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
+    }
+
+    #[test]
+    fn slow_days_are_cached() {
+        let context = get_test_context("slow-days");
+        let day = get_day(14).unwrap();
+        let source = Source::Example(1);
+
+        let first = run(&day, &source, &context).unwrap();
+        let second = run(&day, &source, &context).unwrap();
+        assert!(!first.cached);
+        assert!(second.cached);
+        assert_eq!(first.answers, second.answers);
+
+        let forced = Context {
+            force: true,
+            ..context
+        };
+        assert!(!run(&day, &source, &forced).unwrap().cached);
+
+        let changed = (14, day.1, "fn changed() {}");
+        assert!(!run(&changed, &source, &forced).unwrap().cached);
+    }
+
+    #[test]
+    fn failing_days_are_isolated() {
+        let context = Context {
+            timeout: Duration::from_millis(50),
+            ..get_test_context("isolated")
+        };
+        let days: [Day; 3] = [
+            (1, |_, _| panic!("attempt to subtract with overflow"), ""),
+            (
+                2,
+                |_, _| loop {
+                    std::thread::sleep(Duration::from_secs(1))
+                },
+                "",
+            ),
+            (3, |input, _| Ok(vec![input.to_string()].into()), ""),
+        ];
+
+        let rows: Vec<_> = days
+            .iter()
+            .map(|day| match run(day, &Source::Example(1), &context) {
+                Ok(run) => get_answer_rows(day.0, &Source::Example(1), &run).remove(0),
+                Err(error) => get_error_row(day.0, &Source::Example(1), error.as_ref()),
+            })
+            .collect();
+
+        assert_eq!(rows[0].status, Status::Panic);
+        assert_eq!(
+            rows[0].message.as_deref(),
+            Some("PANIC: attempt to subtract with overflow")
+        );
+        assert_eq!(rows[1].status, Status::Timeout);
+        assert_eq!(rows[2].status, Status::Ok);
+    }
+
+    #[test]
+    fn days_without_examples_are_unchecked() {
+        let rows = get_verify_rows(
+            &[get_day(1).unwrap()],
+            |_| vec![],
+            &get_test_context("unchecked"),
+        );
+
+        assert_eq!(rows, vec![get_unchecked_row(1)]);
+        assert!(get_result(&rows).is_ok());
+    }
+
+    #[test]
+    fn select_days() {
+        let arg = |arg: &str| Some(arg.to_string());
+
+        assert_eq!(super::select_days(None).unwrap().len(), DAYS.len());
+        assert_eq!(
+            super::select_days(arg("--format").as_ref()).unwrap().len(),
+            DAYS.len()
+        );
+        assert_eq!(super::select_days(arg("4").as_ref()).unwrap()[0].0, 4);
+        assert!(super::select_days(arg("26").as_ref()).is_err());
+        assert!(super::select_days(arg("nwe").as_ref())
+            .unwrap_err()
+            .starts_with("unknown command or day \"nwe\""));
+    }
+
+    #[test]
+    fn get_median() {
+        let millis = |values: &[u64]| {
+            values
+                .iter()
+                .map(|&value| Duration::from_millis(value))
+                .collect()
+        };
+
+        assert_eq!(
+            super::get_median(millis(&[5, 1, 3])),
+            Duration::from_millis(3)
+        );
+        assert_eq!(super::get_median(millis(&[7])), Duration::from_millis(7));
+    }
 }
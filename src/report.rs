@@ -0,0 +1,332 @@
+use std::fmt;
+use std::time::Duration;
+
+const COLUMNS: [&str; 8] = [
+    "day",
+    "part",
+    "source",
+    "answer",
+    "status",
+    "message",
+    "duration_ms",
+    "fingerprint",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Status {
+    Ok,
+    Fail,
+    Error,
+    Timeout,
+    Panic,
+    Unchecked,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Row {
+    pub day: u32,
+    pub part: Option<usize>,
+    pub source: String,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub message: Option<String>,
+    pub duration: Duration,
+    pub fingerprint: Option<String>,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Fail => write!(f, "fail"),
+            Status::Error => write!(f, "error"),
+            Status::Timeout => write!(f, "timeout"),
+            Status::Panic => write!(f, "panic"),
+            Status::Unchecked => write!(f, "unchecked"),
+        }
+    }
+}
+
+impl Row {
+    fn fields(&self) -> [Option<String>; 8] {
+        [
+            Some(self.day.to_string()),
+            self.part.map(|part| part.to_string()),
+            Some(self.source.clone()),
+            self.answer.clone(),
+            Some(self.status.to_string()),
+            self.message.clone(),
+            Some(format!("{:.3}", self.duration.as_secs_f64() * 1000.0)),
+            self.fingerprint.clone(),
+        ]
+    }
+}
+
+pub fn parse_format(args: &[String]) -> Result<Option<Format>, String> {
+    let Some(idx) = args.iter().position(|arg| arg == "--format") else {
+        return Ok(None);
+    };
+
+    match args.get(idx + 1).map(String::as_str) {
+        Some("table") => Ok(Some(Format::Table)),
+        Some("json") => Ok(Some(Format::Json)),
+        Some("csv") => Ok(Some(Format::Csv)),
+        other => Err(format!(
+            "unknown format {:?}, expected json, csv or table",
+            other.unwrap_or("")
+        )),
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            ch if (ch as u32) < 0x20 => format!("\\u{:04x}", ch as u32),
+            ch => ch.to_string(),
+        })
+        .collect()
+}
+
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn to_json(row: &Row) -> String {
+    let values: Vec<_> = COLUMNS
+        .iter()
+        .zip(row.fields())
+        .map(|(&column, value)| {
+            let value = match value {
+                None => "null".to_string(),
+                Some(value) if ["day", "part", "duration_ms"].contains(&column) => value,
+                Some(value) => format!("\"{}\"", escape_json(&value)),
+            };
+            format!("\"{}\":{}", column, value)
+        })
+        .collect();
+
+    format!("{{{}}}", values.join(","))
+}
+
+fn to_table(rows: &[Row]) -> String {
+    let cells: Vec<Vec<String>> = std::iter::once(COLUMNS.map(str::to_string).to_vec())
+        .chain(rows.iter().map(|row| {
+            row.fields()
+                .into_iter()
+                .map(|value| value.unwrap_or_default())
+                .collect()
+        }))
+        .collect();
+
+    align(&cells)
+}
+
+fn align(cells: &[Vec<String>]) -> String {
+    let widths: Vec<_> = (0..cells[0].len())
+        .map(|column| cells.iter().map(|row| row[column].len()).max().unwrap())
+        .collect();
+
+    let format_row = |row: &[String]| {
+        let padded: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell))
+            .collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+    let separator: Vec<_> = widths.iter().map(|&width| "-".repeat(width)).collect();
+
+    format_row(&cells[0])
+        + &format_row(&separator)
+        + &cells[1..]
+            .iter()
+            .map(|row| format_row(row))
+            .collect::<String>()
+}
+
+fn get_cell(rows: &[Row], day: u32, part: Option<usize>, source: &str) -> String {
+    rows.iter()
+        .find(|row| {
+            row.day == day && row.source == source && (row.part.is_none() || row.part == part)
+        })
+        .map_or("-".to_string(), |row| match row.status {
+            Status::Ok => "ok".to_string(),
+            Status::Fail => "FAIL".to_string(),
+            Status::Error => "ERROR".to_string(),
+            Status::Timeout => "TIMEOUT".to_string(),
+            Status::Panic => "PANIC".to_string(),
+            Status::Unchecked => "?".to_string(),
+        })
+}
+
+pub fn render_matrix(rows: &[Row], sources: &[String]) -> String {
+    let mut keys: Vec<_> = rows.iter().map(|row| (row.day, row.part)).collect();
+    keys.sort();
+    keys.dedup();
+    let with_parts: Vec<_> = keys
+        .iter()
+        .filter(|(_, part)| part.is_some())
+        .map(|&(day, _)| day)
+        .collect();
+    keys.retain(|(day, part)| part.is_some() || !with_parts.contains(day));
+
+    let header = ["day", "part"]
+        .iter()
+        .map(|column| column.to_string())
+        .chain(sources.iter().cloned())
+        .collect();
+    let cells: Vec<Vec<String>> = std::iter::once(header)
+        .chain(keys.iter().map(|&(day, part)| {
+            [
+                format!("{:02}", day),
+                part.map_or("-".to_string(), |part| part.to_string()),
+            ]
+            .into_iter()
+            .chain(
+                sources
+                    .iter()
+                    .map(|source| get_cell(rows, day, part, source)),
+            )
+            .collect()
+        }))
+        .collect();
+
+    align(&cells)
+}
+
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Table => to_table(rows),
+        Format::Json => {
+            let rows: Vec<_> = rows.iter().map(to_json).collect();
+            format!("[\n{}\n]\n", rows.join(",\n"))
+        }
+        Format::Csv => std::iter::once(COLUMNS.join(",") + "\n")
+            .chain(rows.iter().map(|row| {
+                let fields: Vec<_> = row
+                    .fields()
+                    .into_iter()
+                    .map(|value| escape_csv(&value.unwrap_or_default()))
+                    .collect();
+                fields.join(",") + "\n"
+            }))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: 1,
+                part: Some(1),
+                source: "input".to_string(),
+                answer: Some("142".to_string()),
+                status: Status::Ok,
+                message: None,
+                duration: Duration::from_micros(1500),
+                fingerprint: Some("abc".to_string()),
+            },
+            Row {
+                day: 2,
+                part: None,
+                source: "input".to_string(),
+                answer: None,
+                status: Status::Error,
+                message: Some("no input found at \"a, b\"".to_string()),
+                duration: Duration::ZERO,
+                fingerprint: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn parse_format() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(super::parse_format(&args(&["aoc23", "1"])), Ok(None));
+        assert_eq!(
+            super::parse_format(&args(&["aoc23", "--format", "csv"])),
+            Ok(Some(Format::Csv))
+        );
+        assert!(super::parse_format(&args(&["aoc23", "--format", "xml"])).is_err());
+    }
+
+    #[test]
+    fn render_json() {
+        assert_eq!(
+            render(&get_rows(), Format::Json),
+            r#"[
+{"day":1,"part":1,"source":"input","answer":"142","status":"ok","message":null,"duration_ms":1.500,"fingerprint":"abc"},
+{"day":2,"part":null,"source":"input","answer":null,"status":"error","message":"no input found at \"a, b\"","duration_ms":0.000,"fingerprint":null}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn render_csv() {
+        assert_eq!(
+            render(&get_rows(), Format::Csv),
+            "day,part,source,answer,status,message,duration_ms,fingerprint
+1,1,input,142,ok,,1.500,abc
+2,,input,,error,\"no input found at \"\"a, b\"\"\",0.000,
+"
+        );
+    }
+
+    #[test]
+    fn render_matrix() {
+        let mut rows = get_rows();
+        rows.push(Row {
+            source: "other".to_string(),
+            status: Status::Fail,
+            ..rows[0].clone()
+        });
+        rows.push(Row {
+            part: Some(2),
+            status: Status::Unchecked,
+            ..rows[0].clone()
+        });
+
+        assert_eq!(
+            super::render_matrix(&rows, &["input".to_string(), "other".to_string()]),
+            "day  part  input  other
+---  ----  -----  -----
+01   1     ok     FAIL
+01   2     ?      -
+02   -     ERROR  -
+"
+        );
+    }
+
+    #[test]
+    fn render_table() {
+        let table = render(&get_rows()[..1], Format::Table);
+
+        assert_eq!(
+            table,
+            "day  part  source  answer  status  message  duration_ms  fingerprint
+---  ----  ------  ------  ------  -------  -----------  -----------
+1    1     input   142     ok               1.500        abc
+"
+        );
+    }
+}
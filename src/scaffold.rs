@@ -0,0 +1,249 @@
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::input::get_answers_path;
+
+const TEMPLATE_PATH: &str = "src/day00.rs";
+const MAIN_PATH: &str = "src/main.rs";
+const REGISTRY_START: &str = "const DAYS: &[Day] = &[";
+const REGISTRY_END: &str = "];";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u32),
+    AlreadyExists(PathBuf),
+    MissingRegistry(&'static str),
+    Io(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "day {} is not between 1 and 25", day),
+            ScaffoldError::AlreadyExists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::MissingRegistry(what) => {
+                write!(f, "could not find {} in {}", what, MAIN_PATH)
+            }
+            ScaffoldError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+fn get_registered_day(line: &str, prefix: &str) -> Option<u32> {
+    line.trim()
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|day| day.parse().ok())
+}
+
+fn find_registry(lines: &[String]) -> Result<Range<usize>, ScaffoldError> {
+    let start = lines
+        .iter()
+        .position(|line| line == REGISTRY_START)
+        .ok_or(ScaffoldError::MissingRegistry(REGISTRY_START))?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line == REGISTRY_END)
+        .ok_or(ScaffoldError::MissingRegistry(REGISTRY_END))?;
+
+    Ok(start + 1..start + end)
+}
+
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    range: Range<usize>,
+    day: u32,
+    prefix: &'static str,
+    entry: String,
+) -> Result<(), ScaffoldError> {
+    let commented_out = range.clone().find(|&idx| {
+        let line = lines[idx].trim().strip_prefix("//").unwrap_or_default();
+        get_registered_day(line, prefix) == Some(day)
+    });
+    if let Some(idx) = commented_out {
+        lines[idx] = entry;
+        return Ok(());
+    }
+
+    let registered: Vec<_> = range
+        .filter_map(|idx| get_registered_day(&lines[idx], prefix).map(|other| (idx, other)))
+        .collect();
+
+    let idx = match registered.iter().rev().find(|(_, other)| *other < day) {
+        Some(&(idx, _)) => idx + 1,
+        None => {
+            registered
+                .first()
+                .ok_or(ScaffoldError::MissingRegistry(prefix))?
+                .0
+        }
+    };
+    lines.insert(idx, entry);
+    Ok(())
+}
+
+fn register(main_rs: &str, day: u32) -> Result<String, ScaffoldError> {
+    let mut lines: Vec<_> = main_rs.lines().map(str::to_string).collect();
+
+    let registry = find_registry(&lines)?;
+    insert_sorted(
+        &mut lines,
+        registry.clone(),
+        day,
+        "(",
+        format!("    ({day}, day{day:02}::solve, include_str!(\"day{day:02}.rs\")),"),
+    )?;
+    insert_sorted(
+        &mut lines,
+        0..registry.start,
+        day,
+        "mod day",
+        format!("mod day{day:02};"),
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), ScaffoldError> {
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, content))
+        .map_err(|error| ScaffoldError::Io(path.to_path_buf(), error))
+}
+
+pub fn new_day(
+    day: u32,
+    input_path: &Path,
+    example_path: &Path,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+
+    let source_path = PathBuf::from(format!("src/day{day:02}.rs"));
+    if source_path.exists() {
+        return Err(ScaffoldError::AlreadyExists(source_path));
+    }
+
+    let main_path = Path::new(MAIN_PATH);
+    let registered = register(&read(main_path)?, day)?;
+    write(&source_path, &read(Path::new(TEMPLATE_PATH))?)?;
+    write(main_path, &registered)?;
+
+    let mut created = vec![source_path];
+    for path in [
+        input_path.to_path_buf(),
+        get_answers_path(input_path),
+        example_path.to_path_buf(),
+        get_answers_path(example_path),
+    ] {
+        if !path.exists() {
+            write(&path, "")?;
+            created.push(path);
+        }
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_RS: &str = r#"mod day01;
+//mod day06;
+mod day09;
+mod input;
+
+const DAYS: &[Day] = &[
+    (1, day01::solve, include_str!("day01.rs")),
+    //(6, day06::solve, include_str!("day06.rs")),
+    (9, day09::solve, include_str!("day09.rs")),
+];
+
+fn main() {
+    let days: [Day; 1] = [
+        (3, |_| vec![], ""),
+    ];
+}
+"#;
+
+    #[test]
+    fn register() {
+        assert_eq!(
+            super::register(MAIN_RS, 5).unwrap(),
+            r#"mod day01;
+mod day05;
+//mod day06;
+mod day09;
+mod input;
+
+const DAYS: &[Day] = &[
+    (1, day01::solve, include_str!("day01.rs")),
+    (5, day05::solve, include_str!("day05.rs")),
+    //(6, day06::solve, include_str!("day06.rs")),
+    (9, day09::solve, include_str!("day09.rs")),
+];
+
+fn main() {
+    let days: [Day; 1] = [
+        (3, |_| vec![], ""),
+    ];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn register_commented_out() {
+        let registered = super::register(MAIN_RS, 6).unwrap();
+
+        assert!(registered.contains("mod day01;\nmod day06;\nmod day09;"));
+        assert!(
+            registered.contains("\n    (6, day06::solve, include_str!(\"day06.rs\")),\n    (9,")
+        );
+        assert!(!registered.contains("//"));
+    }
+
+    #[test]
+    fn register_last() {
+        let registered = super::register(MAIN_RS, 12).unwrap();
+
+        assert!(registered.contains("mod day09;\nmod day12;\nmod input;"));
+        assert!(registered.contains("    (12, day12::solve, include_str!(\"day12.rs\")),\n];"));
+    }
+
+    #[test]
+    fn register_main_rs() {
+        let main_rs = include_str!("main.rs");
+        let day = (1..=25)
+            .find(|day| !main_rs.contains(&format!("mod day{day:02};")))
+            .unwrap();
+        let entry = format!("    ({day}, day{day:02}::solve, include_str!(\"day{day:02}.rs\")),");
+
+        let lines: Vec<_> = super::register(main_rs, day)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let registry = find_registry(&lines).unwrap();
+
+        assert_eq!(lines.iter().filter(|line| **line == entry).count(), 1);
+        assert!(lines[registry].contains(&entry));
+    }
+
+    #[test]
+    fn register_without_registry() {
+        assert!(matches!(
+            super::register("fn main() {}", 3),
+            Err(ScaffoldError::MissingRegistry(_))
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+const GLOBAL_FLAGS: [&str; 1] = ["--force"];
+const GLOBAL_VALUES: [&str; 7] = [
+    "--input",
+    "--example",
+    "--set",
+    "--format",
+    "--timeout",
+    "--jobs",
+    "--runs",
+];
+
+pub type Solver = fn(&str, &Options) -> Result<Solution, String>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub answers: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    values: BTreeMap<String, Option<String>>,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Options {
+        let mut values = BTreeMap::new();
+        let mut args = args.iter().skip(1).peekable();
+
+        while let Some(arg) = args.next() {
+            if GLOBAL_VALUES.contains(&arg.as_str()) {
+                args.next();
+            } else if arg.starts_with("--") && !GLOBAL_FLAGS.contains(&arg.as_str()) {
+                let value = args.next_if(|value| !value.starts_with("--"));
+                values.insert(arg.clone(), value.cloned());
+            }
+        }
+
+        Options { values }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.values.keys().map(String::as_str).collect()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<&str>, String> {
+        match self.values.get(name) {
+            None => Ok(None),
+            Some(Some(value)) => Ok(Some(value)),
+            Some(None) => Err(format!("{} needs a value", name)),
+        }
+    }
+
+    pub fn parse_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.get(name)?
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value {:?} for {}", value, name))
+            })
+            .transpose()
+    }
+}
+
+impl Solution {
+    pub fn note(&mut self, note: impl Into<String>) {
+        self.notes.extend(note.into().lines().map(str::to_string));
+    }
+}
+
+impl From<Vec<String>> for Solution {
+    fn from(answers: Vec<String>) -> Solution {
+        Solution {
+            answers,
+            notes: vec![],
+        }
+    }
+}
+
+impl<const N: usize> From<[(&str, Option<&str>); N]> for Options {
+    fn from(values: [(&str, Option<&str>); N]) -> Options {
+        Options {
+            values: values
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.map(str::to_string)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let options = Options::parse(&args(&[
+            "aoc23", "2", "--format", "csv", "--stats", "--vary", "red", "--force", "--trace",
+        ]));
+
+        assert_eq!(options.names(), vec!["--stats", "--trace", "--vary"]);
+        assert!(options.flag("--stats"));
+        assert!(!options.flag("--format"));
+        assert_eq!(options.get("--vary"), Ok(Some("red")));
+        assert!(Options::parse(&args(&["aoc23", "verify", "--jobs", "2"])).is_empty());
+    }
+
+    #[test]
+    fn note() {
+        let mut solution = Solution::from(vec!["142".to_string()]);
+        solution.note("a/b\nc/d\n");
+        solution.note(format!("degree {}", 2));
+
+        assert_eq!(solution.notes, vec!["a/b", "c/d", "degree 2"]);
+    }
+
+    #[test]
+    fn get() {
+        let options = Options::from([("--vary", Some("red")), ("--bag", None)]);
+
+        assert_eq!(options.get("--vary"), Ok(Some("red")));
+        assert_eq!(options.get("--missing"), Ok(None));
+        assert_eq!(options.get("--bag"), Err("--bag needs a value".to_string()));
+    }
+
+    #[test]
+    fn parse_value() {
+        let options = Options::from([("--offset", Some("-50")), ("--number", Some("x"))]);
+
+        assert_eq!(options.parse_value::<i64>("--offset"), Ok(Some(-50)));
+        assert_eq!(options.parse_value::<i64>("--missing"), Ok(None));
+        assert_eq!(
+            options.parse_value::<u32>("--number"),
+            Err("invalid value \"x\" for --number".to_string())
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::client::{Client, ClientError};
+
+const WRONG_ANSWER_LOCKOUT: u64 = 60;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    Wait(u64),
+    WrongLevel,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Attempt {
+    pub time: u64,
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Outcome),
+    AtLeast(String),
+    AtMost(String),
+    Wait(u64),
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Client(ClientError),
+    UnknownResponse,
+    Io(PathBuf, std::io::Error),
+}
+
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl std::str::FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "wrong" => Ok(Outcome::Wrong),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Outcome::Wait)
+                .ok_or(()),
+        }
+    }
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(outcome) => write!(f, "answer was already rejected ({})", outcome),
+            Refusal::AtLeast(bound) => write!(f, "answer must be greater than {}", bound),
+            Refusal::AtMost(bound) => write!(f, "answer must be less than {}", bound),
+            Refusal::Wait(seconds) => write!(f, "wait {}s before submitting again", seconds),
+        }
+    }
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            SubmitError::Client(error) => write!(f, "{}", error),
+            SubmitError::UnknownResponse => write!(f, "could not understand the response"),
+            SubmitError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<ClientError> for SubmitError {
+    fn from(error: ClientError) -> Self {
+        SubmitError::Client(error)
+    }
+}
+
+fn parse_duration(text: &str) -> Option<u64> {
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    text.split_whitespace()
+        .map(|token| {
+            [("h", 3600), ("m", 60), ("s", 1)]
+                .into_iter()
+                .find_map(|(unit, seconds)| {
+                    let value: u64 = token.strip_suffix(unit)?.parse().ok()?;
+                    value.checked_mul(seconds)
+                })
+        })
+        .try_fold(0, |total: u64, seconds| total.checked_add(seconds?))
+}
+
+pub fn parse_response(html: &str) -> Option<Outcome> {
+    if html.contains("That's the right answer") {
+        Some(Outcome::Correct)
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else {
+            Some(Outcome::Wrong)
+        }
+    } else if html.contains("You gave an answer too recently") {
+        let (_, rest) = html.split_once("You have ")?;
+        let (left, _) = rest.split_once(" left to wait")?;
+        parse_duration(left).map(Outcome::Wait)
+    } else if html.contains("You don't seem to be solving the right level") {
+        Some(Outcome::WrongLevel)
+    } else {
+        None
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.splitn(5, '\t');
+
+    Some(Attempt {
+        time: fields.next()?.parse().ok()?,
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        outcome: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+    })
+}
+
+impl History {
+    pub fn load(path: PathBuf) -> Result<History, SubmitError> {
+        let attempts = match std::fs::read_to_string(&path) {
+            Ok(content) => content.lines().filter_map(parse_attempt).collect(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(error) => return Err(SubmitError::Io(path, error)),
+        };
+
+        Ok(History { path, attempts })
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts: Vec<_> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part)
+            .collect();
+
+        if let Some(correct) = attempts
+            .iter()
+            .find(|attempt| attempt.outcome == Outcome::Correct)
+        {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(last) = attempts.last() {
+            let lockout = match last.outcome {
+                Outcome::Wait(seconds) => seconds,
+                Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow => WRONG_ANSWER_LOCKOUT,
+                _ => 0,
+            };
+            if now < last.time + lockout {
+                return Err(Refusal::Wait(last.time + lockout - now));
+            }
+        }
+
+        if let Some(known) = attempts.iter().find(|attempt| {
+            attempt.answer == answer
+                && matches!(
+                    attempt.outcome,
+                    Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow
+                )
+        }) {
+            return Err(Refusal::KnownWrong(known.outcome.clone()));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        for attempt in &attempts {
+            let Ok(bound) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+            match attempt.outcome {
+                Outcome::TooHigh if value >= bound => {
+                    return Err(Refusal::AtMost(attempt.answer.clone()))
+                }
+                Outcome::TooLow if value <= bound => {
+                    return Err(Refusal::AtLeast(attempt.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        let io_error = |error| SubmitError::Io(self.path.clone(), error);
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                writeln!(
+                    file,
+                    "{}\t{}\t{}\t{}\t{}",
+                    attempt.time, attempt.day, attempt.part, attempt.outcome, attempt.answer
+                )
+            })
+            .map_err(io_error)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    (day, part): (u32, u32),
+    answer: &str,
+    now: u64,
+) -> Result<Outcome, SubmitError> {
+    history
+        .check(day, part, answer, now)
+        .map_err(SubmitError::Refused)?;
+
+    let response = client.post_form(
+        &format!("{}/answer", client.day_url(day)),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+    let outcome = parse_response(&response).ok_or(SubmitError::UnknownResponse)?;
+
+    history.record(Attempt {
+        time: now,
+        day,
+        part,
+        outcome: outcome.clone(),
+        answer: answer.to_string(),
+    })?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+
+    fn get_history(name: &str, attempts: &[(u64, Outcome, &str)]) -> History {
+        History {
+            path: stub::get_temp_dir(name).join("submissions.tsv"),
+            attempts: attempts
+                .iter()
+                .map(|(time, outcome, answer)| Attempt {
+                    time: *time,
+                    day: 1,
+                    part: 1,
+                    outcome: outcome.clone(),
+                    answer: answer.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parse_duration() {
+        assert_eq!(super::parse_duration("1h 2m 5s"), Some(3725));
+        assert_eq!(super::parse_duration("42s"), Some(42));
+        assert_eq!(super::parse_duration(""), None);
+        assert_eq!(super::parse_duration("5"), None);
+        assert_eq!(super::parse_duration("5é"), None);
+        assert_eq!(super::parse_duration("ms"), None);
+        assert_eq!(super::parse_duration("99999999999999999h"), None);
+    }
+
+    #[test]
+    fn parse_response() {
+        assert_eq!(
+            super::parse_response("<p>That's the right answer!  You are one gold star"),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            super::parse_response("<p>That's not the right answer; your answer is too low."),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            super::parse_response("<p>That's not the right answer.  If you're stuck"),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            super::parse_response(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ),
+            Some(Outcome::Wait(65))
+        );
+        assert_eq!(
+            super::parse_response(
+                "<p>You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Outcome::WrongLevel)
+        );
+        assert_eq!(super::parse_response("<html></html>"), None);
+    }
+
+    #[test]
+    fn history_check() {
+        let history = get_history(
+            "history-check",
+            &[
+                (100, Outcome::TooHigh, "500"),
+                (200, Outcome::TooLow, "100"),
+                (300, Outcome::Wrong, "abc"),
+            ],
+        );
+
+        assert_eq!(history.check(1, 1, "250", 330), Err(Refusal::Wait(30)));
+        assert_eq!(
+            history.check(1, 1, "abc", 1000),
+            Err(Refusal::KnownWrong(Outcome::Wrong))
+        );
+        assert_eq!(
+            history.check(1, 1, "600", 1000),
+            Err(Refusal::AtMost("500".to_string()))
+        );
+        assert_eq!(
+            history.check(1, 1, "90", 1000),
+            Err(Refusal::AtLeast("100".to_string()))
+        );
+        assert_eq!(history.check(1, 1, "250", 1000), Ok(()));
+        assert_eq!(history.check(1, 2, "600", 1000), Ok(()));
+    }
+
+    #[test]
+    fn history_roundtrip() {
+        let mut history = get_history("history-roundtrip", &[]);
+        history
+            .record(Attempt {
+                time: 42,
+                day: 1,
+                part: 2,
+                outcome: Outcome::Wait(30),
+                answer: "281".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            History::load(history.path.clone()).unwrap().attempts,
+            history.attempts
+        );
+    }
+
+    #[test]
+    fn submit() {
+        let (base_url, server) =
+            stub::serve(vec![(200, "<p>That's the right answer!".to_string())]);
+        let client = stub::get_test_client(&base_url, "submit");
+        let mut history = get_history("submit", &[]);
+
+        assert_eq!(
+            super::submit(&client, &mut history, (1, 2), "281", 1000).unwrap(),
+            Outcome::Correct
+        );
+        assert!(matches!(
+            super::submit(&client, &mut history, (1, 2), "281", 2000),
+            Err(SubmitError::Refused(Refusal::AlreadySolved(_)))
+        ));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/1/answer "));
+        assert!(requests[0].ends_with("level=2&answer=281"));
+    }
+}
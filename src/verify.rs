@@ -0,0 +1,72 @@
+use crate::examples;
+use crate::input::{self, Source};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Check {
+    pub day: u32,
+    pub source: Source,
+    pub part: usize,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Some(&self.expected)
+    }
+}
+
+pub fn get_sources(day: u32) -> Vec<Source> {
+    examples::list(day)
+        .into_iter()
+        .map(Source::Example)
+        .chain([Source::Default])
+        .filter(|source| {
+            source
+                .path(day)
+                .is_some_and(|path| input::read_answers(&path).iter().any(Option::is_some))
+        })
+        .collect()
+}
+
+pub fn check(day: u32, source: &Source, answers: &[String]) -> Vec<Check> {
+    source
+        .path(day)
+        .map(|path| input::read_answers(&path))
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, expected)| {
+            expected.map(|expected| Check {
+                day,
+                source: source.clone(),
+                part: idx + 1,
+                expected,
+                actual: answers.get(idx).cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_passed() {
+        let check = Check {
+            day: 1,
+            source: Source::Example(2),
+            part: 2,
+            expected: "281".to_string(),
+            actual: Some("142".to_string()),
+        };
+
+        assert!(!check.passed());
+        assert!(Check {
+            actual: Some("281".to_string()),
+            ..check
+        }
+        .passed());
+    }
+}
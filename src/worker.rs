@@ -0,0 +1,165 @@
+use std::fmt;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WorkerError {
+    Timeout(Duration),
+    Panic(String),
+}
+
+impl fmt::Display for WorkerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorkerError::Timeout(timeout) => write!(f, "TIMEOUT after {:?}", timeout),
+            WorkerError::Panic(message) => write!(f, "PANIC: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}
+
+pub fn parse_timeout(args: &[String]) -> Result<Duration, String> {
+    let Some(idx) = args.iter().position(|arg| arg == "--timeout") else {
+        return Ok(DEFAULT_TIMEOUT);
+    };
+
+    args.get(idx + 1)
+        .and_then(|secs| secs.parse::<f64>().ok())
+        .filter(|&secs| secs > 0.0 && secs.is_finite())
+        .map(Duration::from_secs_f64)
+        .ok_or("--timeout needs a positive number of seconds".to_string())
+}
+
+pub fn parse_jobs(args: &[String]) -> Result<usize, String> {
+    let Some(idx) = args.iter().position(|arg| arg == "--jobs") else {
+        return Ok(std::thread::available_parallelism().map_or(1, usize::from));
+    };
+
+    args.get(idx + 1)
+        .and_then(|jobs| jobs.parse().ok())
+        .filter(|&jobs| jobs > 0)
+        .ok_or("--jobs needs a positive number".to_string())
+}
+
+fn get_panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+pub fn run_isolated<T, F>(task: F, timeout: Duration) -> Result<T, WorkerError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + UnwindSafe + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(std::panic::catch_unwind(task));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(payload)) => Err(WorkerError::Panic(get_panic_message(payload.as_ref()))),
+        Err(_) => Err(WorkerError::Timeout(timeout)),
+    }
+}
+
+pub fn map_parallel<T, R, F>(items: &[T], jobs: usize, task: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = task(item);
+                results.lock().unwrap().push((idx, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|&(idx, _)| idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timeout() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(super::parse_timeout(&args(&["aoc23"])), Ok(DEFAULT_TIMEOUT));
+        assert_eq!(
+            super::parse_timeout(&args(&["aoc23", "--timeout", "2.5"])),
+            Ok(Duration::from_millis(2500))
+        );
+        assert!(super::parse_timeout(&args(&["aoc23", "--timeout", "0"])).is_err());
+        assert!(super::parse_timeout(&args(&["aoc23", "--timeout"])).is_err());
+    }
+
+    #[test]
+    fn parse_jobs() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(super::parse_jobs(&args(&["aoc23"])).unwrap() >= 1);
+        assert_eq!(super::parse_jobs(&args(&["aoc23", "--jobs", "3"])), Ok(3));
+        assert!(super::parse_jobs(&args(&["aoc23", "--jobs", "0"])).is_err());
+    }
+
+    #[test]
+    fn map_parallel_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = super::map_parallel(&items, 4, |&item| {
+            std::thread::sleep(Duration::from_millis(20 - item));
+            item * item
+        });
+
+        assert_eq!(
+            squares,
+            items.iter().map(|item| item * item).collect::<Vec<_>>()
+        );
+        assert!(super::map_parallel(&[] as &[u64], 4, |&item| item).is_empty());
+    }
+
+    #[test]
+    fn run_isolated() {
+        assert_eq!(super::run_isolated(|| 42, DEFAULT_TIMEOUT), Ok(42));
+    }
+
+    #[test]
+    fn run_isolated_panic() {
+        let result = super::run_isolated(|| -> u32 { panic!("day {} broke", 4) }, DEFAULT_TIMEOUT);
+
+        assert_eq!(result, Err(WorkerError::Panic("day 4 broke".to_string())));
+    }
+
+    #[test]
+    fn run_isolated_timeout() {
+        let timeout = Duration::from_millis(20);
+        let result = super::run_isolated(move || std::thread::sleep(timeout * 10), timeout);
+
+        assert_eq!(result, Err(WorkerError::Timeout(timeout)));
+        assert_eq!(result.unwrap_err().to_string(), "TIMEOUT after 20ms");
+    }
+}
@@ -0,0 +1,43 @@
+use std::process::Command;
+
+fn run_json(args: &[&str]) -> Vec<serde_json::Value> {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc23"))
+        .args(args)
+        .args(["--format", "json"])
+        .env_remove("AOC_SESSION")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    match serde_json::from_str(&stdout) {
+        Ok(serde_json::Value::Array(rows)) => rows,
+        _ => panic!("stdout of {:?} is not a json array:\n{}", args, stdout),
+    }
+}
+
+#[test]
+fn json_output_is_machine_readable() {
+    for args in [["25", "--example", "1"], ["20", "--example", "1"]] {
+        let rows = run_json(&args);
+
+        assert!(!rows.is_empty(), "{:?}", args);
+        assert!(rows.iter().all(|row| row["day"].is_u64()), "{:?}", args);
+    }
+}
+
+#[test]
+fn json_verify_output_is_machine_readable() {
+    let rows = run_json(&["verify"]);
+
+    assert!(rows.iter().any(|row| row["status"] == "ok"));
+}
+
+#[test]
+fn parallel_output_is_day_ordered() {
+    let days: Vec<_> = run_json(&["verify", "--jobs", "8"])
+        .iter()
+        .map(|row| row["day"].as_u64().unwrap())
+        .collect();
+
+    assert!(days.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", days);
+}